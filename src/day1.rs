@@ -1,24 +1,32 @@
+use crate::util::ParseError;
+
+/// Parses the instructions into the number of floors that each one moves Santa up.
 #[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> String {
-    input.to_string()
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .char_indices()
+        .map(|(i, c)| match c {
+            '(' => Ok(1),
+            ')' => Ok(-1),
+            _ => Err(ParseError::unknown(
+                input,
+                &input[i..i + c.len_utf8()],
+                "instruction",
+            )),
+        })
+        .collect()
 }
 
 #[aoc(day1, part1)]
-pub fn part1(input: &str) -> i32 {
-    let up = input.chars().filter(|&c| c == '(').count() as i32;
-    let down = input.chars().filter(|&c| c == ')').count() as i32;
-    up - down
+pub fn part1(input: &[i32]) -> i32 {
+    input.iter().sum()
 }
 
 #[aoc(day1, part2)]
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &[i32]) -> usize {
     let mut position = 0;
-    for (i, c) in input.chars().enumerate() {
-        position += match c {
-            '(' => 1,
-            ')' => -1,
-            c => panic!("unknown input character: {}", c),
-        };
+    for (i, step) in input.iter().enumerate() {
+        position += step;
         if position < 0 {
             return i + 1;
         }
@@ -30,18 +38,30 @@ pub fn part2(input: &str) -> usize {
 mod tests {
     use super::*;
 
+    fn parse(input: &str) -> Vec<i32> {
+        input_generator(input).unwrap()
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1("(())"), 0);
-        assert_eq!(part1("((("), 3);
-        assert_eq!(part1("(()(()("), 3);
-        assert_eq!(part1("))((((("), 3);
-        assert_eq!(part1("())"), -1);
+        assert_eq!(part1(&parse("(())")), 0);
+        assert_eq!(part1(&parse("(((")), 3);
+        assert_eq!(part1(&parse("(()(()(")), 3);
+        assert_eq!(part1(&parse("))(((((")), 3);
+        assert_eq!(part1(&parse("())")), -1);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(")"), 1);
-        assert_eq!(part2("()())"), 5);
+        assert_eq!(part2(&parse(")")), 1);
+        assert_eq!(part2(&parse("()())")), 5);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            input_generator("(()x)").unwrap_err().to_string(),
+            "1:4: unknown instruction `x`"
+        );
     }
 }
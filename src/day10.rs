@@ -1,6 +1,18 @@
+use crate::util::{end_of, ParseError};
+
 #[aoc_generator(day10)]
-pub fn input_generator(input: &str) -> String {
-    input.to_string()
+pub fn input_generator(input: &str) -> Result<String, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::expected(input, end_of(input), "digits"));
+    }
+    if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(ParseError::unknown(
+            input,
+            &input[i..i + c.len_utf8()],
+            "digit",
+        ));
+    }
+    Ok(input.to_string())
 }
 
 fn look_and_say(digits: &str) -> String {
@@ -55,11 +67,20 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let _input = input_generator(&TEST_INPUT);
         assert_eq!(look_and_say("1"), "11");
         assert_eq!(look_and_say("11"), "21");
         assert_eq!(look_and_say("21"), "1211");
         assert_eq!(look_and_say("1211"), "111221");
         assert_eq!(look_and_say("111221"), "312211");
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error(&TEST_INPUT),
+            "1:1: expected digits, found end of line"
+        );
+        assert_eq!(error("11a"), "1:3: unknown digit `a`");
+    }
 }
@@ -1,11 +1,29 @@
 use std::convert::TryInto;
 use std::iter::FromIterator;
 
+use crate::util::{ParseError, ParseErrorKind};
+
 type Password = [char; 8];
 
 #[aoc_generator(day11)]
-pub fn parse_input(input: &str) -> Password {
-    input.chars().collect::<Vec<_>>().try_into().unwrap()
+pub fn parse_input(input: &str) -> Result<Password, ParseError> {
+    if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(ParseError::unknown(
+            input,
+            &input[i..i + c.len_utf8()],
+            "password letter",
+        ));
+    }
+    input.chars().collect::<Vec<_>>().try_into().map_err(|_| {
+        ParseError::at(
+            input,
+            input,
+            ParseErrorKind::Expected {
+                expected: "8 letters".to_string(),
+                found: Some(format!("{} letters", input.chars().count())),
+            },
+        )
+    })
 }
 
 fn is_valid(password: &Password) -> bool {
@@ -81,36 +99,47 @@ mod tests {
     #[test]
     fn test_next_password() {
         assert_eq!(
-            next_password(parse_input("abcdefgh")),
-            parse_input("abcdefgi")
+            next_password(parse_input("abcdefgh").unwrap()),
+            parse_input("abcdefgi").unwrap()
         );
         assert_eq!(
-            next_password(parse_input("abcdefgz")),
-            parse_input("abcdefha")
+            next_password(parse_input("abcdefgz").unwrap()),
+            parse_input("abcdefha").unwrap()
         );
         assert_eq!(
-            next_password(parse_input("abcdzzzz")),
-            parse_input("abceaaaa")
+            next_password(parse_input("abcdzzzz").unwrap()),
+            parse_input("abceaaaa").unwrap()
         );
     }
 
     #[test]
     fn test_is_valid() {
-        assert!(!is_valid(&parse_input("hijklmmn")));
-        assert!(!is_valid(&parse_input("abbceffg")));
-        assert!(!is_valid(&parse_input("abbcegjk")));
-        assert!(!is_valid(&parse_input("abbcegjk")));
+        assert!(!is_valid(&parse_input("hijklmmn").unwrap()));
+        assert!(!is_valid(&parse_input("abbceffg").unwrap()));
+        assert!(!is_valid(&parse_input("abbcegjk").unwrap()));
+        assert!(!is_valid(&parse_input("abbcegjk").unwrap()));
     }
 
     #[test]
     fn test_part1() {
         assert_eq!(
-            next_valid_password(parse_input("abcdefgh")),
-            parse_input("abcdffaa")
+            next_valid_password(parse_input("abcdefgh").unwrap()),
+            parse_input("abcdffaa").unwrap()
+        );
+        assert_eq!(
+            next_valid_password(parse_input("ghijklmn").unwrap()),
+            parse_input("ghjaabcc").unwrap()
         );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| parse_input(s).unwrap_err().to_string();
+        assert_eq!(error("abc"), "1:1: expected 8 letters, found 3 letters");
         assert_eq!(
-            next_valid_password(parse_input("ghijklmn")),
-            parse_input("ghjaabcc")
+            error("abcdefghi"),
+            "1:1: expected 8 letters, found 9 letters"
         );
+        assert_eq!(error("abcdéfgh"), "1:5: unknown password letter `é`");
     }
 }
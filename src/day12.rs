@@ -1,16 +1,91 @@
 use json::JsonValue;
-use std::convert::TryInto;
+
+use crate::util::{end_of, ParseError, ParseErrorKind};
+
+/// Returns the number tokens of a JSON document, in the order they appear in.
+fn number_tokens(input: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = None;
+    for (i, c) in input.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if start.is_none() && (c == '-' || c.is_ascii_digit()) {
+            // A number starts with a sign or a digit, so the `e` of `true` doesn't start one.
+            start = Some(i);
+        } else if start.is_some() && (c.is_ascii_digit() || "+-.eE".contains(c)) {
+            // The rest of the number, like its fraction and exponent.
+        } else {
+            if let Some(start) = start.take() {
+                tokens.push(&input[start..i]);
+            }
+            in_string = c == '"';
+        }
+    }
+    if let Some(start) = start {
+        tokens.push(&input[start..]);
+    }
+    tokens
+}
+
+/// Calls `f` with every number in the document, in the order they appear in.
+fn for_each_number(json: &JsonValue, f: &mut impl FnMut(&JsonValue)) {
+    match json {
+        JsonValue::Number(_) => f(json),
+        JsonValue::Array(values) => values.iter().for_each(|value| for_each_number(value, f)),
+        JsonValue::Object(object) => object
+            .iter()
+            .for_each(|(_, value)| for_each_number(value, f)),
+        _ => {}
+    }
+}
 
 #[aoc_generator(day12)]
-pub fn parse_input(input: &str) -> JsonValue {
-    json::parse(input).unwrap()
+pub fn parse_input(input: &str) -> Result<JsonValue, ParseError> {
+    let json = parse_json(input)?;
+    // The solutions add up the numbers as integers, so every number has to be one.
+    let mut tokens = number_tokens(input).into_iter();
+    let mut error = None;
+    for_each_number(&json, &mut |number| {
+        let token = tokens.next().unwrap_or(input);
+        if error.is_none() && number.as_i32().is_none() {
+            error = Some(ParseError::invalid(input, token, "number", "not an i32"));
+        }
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(json),
+    }
+}
+
+fn parse_json(input: &str) -> Result<JsonValue, ParseError> {
+    json::parse(input).map_err(|err| match err {
+        json::Error::UnexpectedCharacter { ch, line, column } => ParseError::new(
+            line,
+            column,
+            ParseErrorKind::Unknown {
+                what: "character",
+                token: ch.to_string(),
+            },
+        ),
+        json::Error::UnexpectedEndOfJson => {
+            ParseError::expected(input, end_of(input), "JSON value")
+        }
+        err => ParseError::invalid(input, input, "JSON document", err),
+    })
 }
 
 #[aoc(day12, part1)]
 pub fn part1(json: &JsonValue) -> i32 {
     match json {
-        JsonValue::Number(value) => (*value).try_into().ok().unwrap(),
-        JsonValue::Array(values) => values.iter().map(|x| part1(x)).sum(),
+        JsonValue::Number(_) => json.as_i32().expect("checked when parsing"),
+        JsonValue::Array(values) => values.iter().map(part1).sum(),
         JsonValue::Object(object) => object.iter().map(|(_, value)| part1(value)).sum(),
         _ => 0,
     }
@@ -19,8 +94,8 @@ pub fn part1(json: &JsonValue) -> i32 {
 #[aoc(day12, part2)]
 pub fn part2(json: &JsonValue) -> i32 {
     match json {
-        JsonValue::Number(value) => (*value).try_into().ok().unwrap(),
-        JsonValue::Array(values) => values.iter().map(|x| part2(x)).sum(),
+        JsonValue::Number(_) => json.as_i32().expect("checked when parsing"),
+        JsonValue::Array(values) => values.iter().map(part2).sum(),
         JsonValue::Object(object) => {
             let contains_red = object
                 .iter()
@@ -41,21 +116,48 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&parse_input(r#"[1,2,3]"#)), 6);
-        assert_eq!(part1(&parse_input(r#"{"a":2,"b":4}"#)), 6);
-        assert_eq!(part1(&parse_input(r#"[[[3]]]"#)), 3);
-        assert_eq!(part1(&parse_input(r#"{"a":{"b":4},"c":-1}"#)), 3);
-        assert_eq!(part1(&parse_input(r#"{"a":[-1,1]}"#)), 0);
-        assert_eq!(part1(&parse_input(r#"[-1,{"a":1}]"#)), 0);
-        assert_eq!(part1(&parse_input(r#"[]"#)), 0);
-        assert_eq!(part1(&parse_input(r#"{}"#)), 0);
+        assert_eq!(part1(&parse_input(r#"[1,2,3]"#).unwrap()), 6);
+        assert_eq!(part1(&parse_input(r#"{"a":2,"b":4}"#).unwrap()), 6);
+        assert_eq!(part1(&parse_input(r#"[[[3]]]"#).unwrap()), 3);
+        assert_eq!(part1(&parse_input(r#"{"a":{"b":4},"c":-1}"#).unwrap()), 3);
+        assert_eq!(part1(&parse_input(r#"{"a":[-1,1]}"#).unwrap()), 0);
+        assert_eq!(part1(&parse_input(r#"[-1,{"a":1}]"#).unwrap()), 0);
+        assert_eq!(part1(&parse_input(r#"[]"#).unwrap()), 0);
+        assert_eq!(part1(&parse_input(r#"{}"#).unwrap()), 0);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&parse_input(r#"[1,2,3]"#)), 6);
-        assert_eq!(part2(&parse_input(r#"[1,{"c":"red","b":2},3]"#)), 4);
-        assert_eq!(part2(&parse_input(r#"{"d":"red","e":[1,2,3,4],"f":5}"#)), 0);
-        assert_eq!(part2(&parse_input(r#"[1,"red",5]"#)), 6);
+        assert_eq!(part2(&parse_input(r#"[1,2,3]"#).unwrap()), 6);
+        assert_eq!(
+            part2(&parse_input(r#"[1,{"c":"red","b":2},3]"#).unwrap()),
+            4
+        );
+        assert_eq!(
+            part2(&parse_input(r#"{"d":"red","e":[1,2,3,4],"f":5}"#).unwrap()),
+            0
+        );
+        assert_eq!(part2(&parse_input(r#"[1,"red",5]"#).unwrap()), 6);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| parse_input(s).unwrap_err().to_string();
+        assert_eq!(
+            error("{\"a\": \"1.5\",\n \"b\": [1, 2.5]}"),
+            "2:11: invalid number `2.5`: not an i32"
+        );
+        assert_eq!(
+            error("[1, 3000000000]"),
+            "1:5: invalid number `3000000000`: not an i32"
+        );
+        assert_eq!(
+            error("[true, false, 2.5]"),
+            "1:15: invalid number `2.5`: not an i32"
+        );
+        assert_eq!(
+            error("{\"a\": true, \"b\": -0.5}"),
+            "1:18: invalid number `-0.5`: not an i32"
+        );
     }
 }
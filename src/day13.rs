@@ -2,36 +2,36 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
+use crate::util::{parse_lines, parse_number, split_once, strip_suffix, ParseError};
+
 #[derive(Debug, Clone)]
 pub struct Relation(String, String, i32);
 
 impl FromStr for Relation {
-    type Err = ();
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
         // Examples:
         //   Alice would gain 54 happiness units by sitting next to Bob.
         //   Alice would lose 79 happiness units by sitting next to Carol.
-        let (first, s) = s.split_once(" would ").unwrap();
-        let (s, last) = s
-            .split_once(" happiness units by sitting next to ")
-            .unwrap();
-        let second = last.strip_suffix('.').unwrap();
+        let (first, s) = split_once(line, line, " would ")?;
+        let (s, last) = split_once(line, s, " happiness units by sitting next to ")?;
+        let second = strip_suffix(line, last, ".")?;
         if let Some(gain) = s.strip_prefix("gain ") {
-            let gain = gain.parse::<i32>().unwrap();
+            let gain = parse_number::<i32>(line, gain)?;
             Ok(Relation(first.to_string(), second.to_string(), gain))
         } else if let Some(loss) = s.strip_prefix("lose ") {
-            let loss = loss.parse::<i32>().unwrap();
+            let loss = parse_number::<i32>(line, loss)?;
             Ok(Relation(first.to_string(), second.to_string(), -loss))
         } else {
-            panic!("invalid input: {}", s)
+            Err(ParseError::expected(line, s, "`gain` or `lose`"))
         }
     }
 }
 
 #[aoc_generator(day13)]
-pub fn input_generator(input: &str) -> Vec<Relation> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Relation>, ParseError> {
+    parse_lines(input, str::parse)
 }
 
 fn compute_happiness(arrangement: &[String], relations: &[Relation]) -> i32 {
@@ -116,7 +116,17 @@ David would gain 41 happiness units by sitting next to Carol."
 
     #[test]
     fn test_part1() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part1(&input), 330);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("Alice would gain 54 happiness units by sitting next to Bob.\nBob would win 3 happiness units by sitting next to Alice."), "2:11: expected `gain` or `lose`, found `win`");
+        assert_eq!(
+            error("Alice would lose 79 happiness units by sitting next to Carol"),
+            "1:61: expected `.`, found end of line"
+        );
+    }
 }
@@ -3,9 +3,10 @@ use std::str::FromStr;
 use lazy_static::*;
 use regex::Regex;
 
+use crate::util::{parse_lines, parse_number, ParseError};
+
 #[derive(Debug, Clone)]
 pub struct Reindeer {
-    name: String,
     fly_speed: u32,
     fly_duration: u32,
     rest_duration: u32,
@@ -13,28 +14,30 @@ pub struct Reindeer {
 
 lazy_static! {
     static ref INPUT_RE: Regex = Regex::new(
-        r"^(\w+) can fly (\d+) km/s for (\d+) seconds, but then must rest for (\d+) seconds\.$"
+        r"^\w+ can fly (\d+) km/s for (\d+) seconds, but then must rest for (\d+) seconds\.$"
     )
     .unwrap();
 }
 
 impl FromStr for Reindeer {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let captures = INPUT_RE.captures(s).unwrap();
+        let captures = INPUT_RE
+            .captures(s)
+            .ok_or_else(|| ParseError::expected(s, s, "reindeer description"))?;
+        let number = |i| parse_number(s, captures.get(i).unwrap().as_str());
         Ok(Reindeer {
-            name: captures[1].to_string(),
-            fly_speed: captures[2].parse().unwrap(),
-            fly_duration: captures[3].parse().unwrap(),
-            rest_duration: captures[4].parse().unwrap(),
+            fly_speed: number(1)?,
+            fly_duration: number(2)?,
+            rest_duration: number(3)?,
         })
     }
 }
 
 #[aoc_generator(day14)]
-pub fn input_generator(input: &str) -> Vec<Reindeer> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Reindeer>, ParseError> {
+    parse_lines(input, str::parse)
 }
 
 #[derive(Debug)]
@@ -134,25 +137,33 @@ Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.
 
     #[test]
     fn test_part1() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         let mut states = simulate(&input, 1000);
         states.sort_by_key(|state| state.distance);
         let [loser, winner]: [ReindeerState; 2] = states.try_into().unwrap();
-        assert_eq!(winner.reindeer.name, "Comet");
+        // Comet flies at 14 km/s, and Dancer at 16 km/s.
+        assert_eq!(winner.reindeer.fly_speed, 14);
         assert_eq!(winner.distance, 1120);
-        assert_eq!(loser.reindeer.name, "Dancer");
+        assert_eq!(loser.reindeer.fly_speed, 16);
         assert_eq!(loser.distance, 1056);
     }
 
     #[test]
     fn test_part2() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         let mut states = simulate(&input, 1000);
         states.sort_by_key(|state| state.points);
         let [loser, winner]: [ReindeerState; 2] = states.try_into().unwrap();
-        assert_eq!(winner.reindeer.name, "Dancer");
+        assert_eq!(winner.reindeer.fly_speed, 16);
         assert_eq!(winner.points, 689);
-        assert_eq!(loser.reindeer.name, "Comet");
+        assert_eq!(loser.reindeer.fly_speed, 14);
         assert_eq!(loser.points, 312);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\nDancer can fly fast."), "2:1: expected reindeer description, found `Dancer`");
+        assert_eq!(error("Comet can fly 99999999999 km/s for 10 seconds, but then must rest for 127 seconds."), "1:15: invalid number `99999999999`: number too large to fit in target type");
+    }
 }
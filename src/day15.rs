@@ -4,9 +4,10 @@ use std::str::FromStr;
 use lazy_static::*;
 use regex::Regex;
 
+use crate::util::{end_of, parse_lines, parse_number, ParseError};
+
 #[derive(Debug, Clone)]
 pub struct Ingredient {
-    name: String,
     capacity: i32,
     durability: i32,
     flavor: i32,
@@ -16,30 +17,36 @@ pub struct Ingredient {
 
 lazy_static! {
     static ref INPUT_RE: Regex = Regex::new(
-        r"^(\w+): capacity (-?\d+), durability (-?\d+), flavor (-?\d+), texture (-?\d+), calories (-?\d+)$"
+        r"^\w+: capacity (-?\d+), durability (-?\d+), flavor (-?\d+), texture (-?\d+), calories (-?\d+)$"
     )
     .unwrap();
 }
 
 impl FromStr for Ingredient {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let captures = INPUT_RE.captures(s).unwrap();
+        let captures = INPUT_RE
+            .captures(s)
+            .ok_or_else(|| ParseError::expected(s, s, "ingredient description"))?;
+        let number = |i| parse_number(s, captures.get(i).unwrap().as_str());
         Ok(Ingredient {
-            name: captures[1].to_string(),
-            capacity: captures[2].parse().unwrap(),
-            durability: captures[3].parse().unwrap(),
-            flavor: captures[4].parse().unwrap(),
-            texture: captures[5].parse().unwrap(),
-            calories: captures[6].parse().unwrap(),
+            capacity: number(1)?,
+            durability: number(2)?,
+            flavor: number(3)?,
+            texture: number(4)?,
+            calories: number(5)?,
         })
     }
 }
 
 #[aoc_generator(day15)]
-pub fn input_generator(input: &str) -> Vec<Ingredient> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Ingredient>, ParseError> {
+    let ingredients = parse_lines(input, str::parse)?;
+    if ingredients.is_empty() {
+        return Err(ParseError::expected(input, end_of(input), "ingredient"));
+    }
+    Ok(ingredients)
 }
 
 type Selection<'a> = Vec<(&'a Ingredient, u32)>;
@@ -48,8 +55,8 @@ type SelectionRef<'a> = &'a [(&'a Ingredient, u32)];
 fn select_ingredients(
     ingredients: &[Ingredient],
     teaspoons: u32,
-) -> Box<dyn Iterator<Item = Selection> + '_> {
-    let (first, rest) = ingredients.split_first().unwrap();
+) -> Box<dyn Iterator<Item = Selection<'_>> + '_> {
+    let (first, rest) = ingredients.split_first().expect("checked when parsing");
     if rest.is_empty() {
         let selection = (first, teaspoons);
         Box::new(once(vec![selection]))
@@ -122,13 +129,27 @@ Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3"
 
     #[test]
     fn test_part1() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part1(&input), 62842880);
     }
 
     #[test]
     fn test_part2() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part2(&input), 57600000);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error(""), "1:1: expected ingredient, found end of line");
+        assert_eq!(
+            error("Sugar: capacity 1, durability 2, flavor 3, texture 4, calories 99999999999"),
+            "1:64: invalid number `99999999999`: number too large to fit in target type"
+        );
+        assert_eq!(
+            error("Sugar: capacity 1, durability 2, flavor 3, texture 4, calories 5\nSalt"),
+            "2:1: expected ingredient description, found `Salt`"
+        );
+    }
 }
@@ -3,6 +3,8 @@ use std::str::FromStr;
 
 use lazy_static::*;
 
+use crate::util::{parse_lines, parse_number, split_once, strip_prefix, ParseError};
+
 #[derive(Debug)]
 pub struct Sue {
     number: u32,
@@ -10,20 +12,21 @@ pub struct Sue {
 }
 
 impl FromStr for Sue {
-    type Err = ();
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (number, s) = s.strip_prefix("Sue ").unwrap().split_once(": ").unwrap();
-        let number = number.parse().unwrap();
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let s = strip_prefix(line, line, "Sue ")?;
+        let (number, s) = split_once(line, s, ": ")?;
+        let number = parse_number(line, number)?;
         let possessions = s
             .split(", ")
             .map(|s| {
-                let (compound, amount) = s.split_once(": ").unwrap();
+                let (compound, amount) = split_once(line, s, ": ")?;
                 let compound = compound.to_string();
-                let amount = amount.parse().unwrap();
-                (compound, amount)
+                let amount = parse_number(line, amount)?;
+                Ok((compound, amount))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         Ok(Sue {
             number,
             possessions,
@@ -32,8 +35,8 @@ impl FromStr for Sue {
 }
 
 #[aoc_generator(day16)]
-pub fn input_generator(input: &str) -> Vec<Sue> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Sue>, ParseError> {
+    parse_lines(input, str::parse)
 }
 
 type Sample<'a> = HashMap<&'a str, u32>;
@@ -85,3 +88,21 @@ pub fn part2(input: &[Sue]) -> u32 {
     let gift_sue = input.iter().find(|sue| sue.matches_part2(&SAMPLE)).unwrap();
     gift_sue.number
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("Sue 1: cars: 9\nSue 2 cars: 3"),
+            "2:5: invalid number `2 cars`: invalid digit found in string"
+        );
+        assert_eq!(
+            error("Sue 1: cars: many"),
+            "1:14: invalid number `many`: invalid digit found in string"
+        );
+    }
+}
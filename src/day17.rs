@@ -1,45 +1,94 @@
-use std::cmp::Ordering;
-use std::iter::{empty, once};
+use crate::util::{count_subsets_by_len, parse_lines, parse_number, ParseError};
 
 #[aoc_generator(day17)]
-pub fn input_generator(input: &str) -> Vec<i32> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines(input, |line| parse_number(line, line))
 }
 
-fn ways_to_fill(amount: i32, containers: &[i32]) -> impl Iterator<Item = Vec<i32>> + '_ {
-    containers.iter().copied().enumerate().flat_map(
-        move |(i, container)| -> Box<dyn Iterator<Item = _>> {
-            match container.cmp(&amount) {
-                Ordering::Greater => Box::new(empty()),
-                Ordering::Equal => Box::new(once(vec![container])),
-                Ordering::Less => Box::new(
-                    ways_to_fill(amount - container, &containers[i + 1..]).map(move |mut inner| {
-                        inner.insert(0, container);
-                        inner
-                    }),
-                ),
+/// Counts the ways to fill exactly `amount` with the containers, by the number of containers.
+///
+/// Panics if a container has a negative size, or if a count doesn't fit in a `u128`.
+fn ways_to_fill(amount: i32, containers: &[i32]) -> Vec<u128> {
+    assert!(
+        containers.iter().all(|&size| size >= 0),
+        "containers cannot have a negative size"
+    );
+    if amount < 0 {
+        return vec![0; containers.len() + 1];
+    }
+    // With few containers, like in the puzzle, the subset-sum engine is faster: it takes time
+    // proportional to `2^(n/2)` for `n` containers, however large the amount is.
+    let n = containers.len();
+    if n <= 40 && 2 << (n / 2) < n * most_containers(amount, containers) * (amount as usize + 1) {
+        let counts = count_subsets_by_len(containers, i64::from(amount));
+        counts.into_iter().map(u128::from).collect()
+    } else {
+        fill_table(amount as usize, containers)
+    }
+}
+
+/// Returns the most containers that can fill `amount` together: as many of the smallest ones as
+/// fit.
+fn most_containers(amount: i32, containers: &[i32]) -> usize {
+    let mut sizes = containers.to_vec();
+    sizes.sort_unstable();
+    sizes
+        .iter()
+        .scan(0, |total, &size| {
+            *total += size;
+            Some(*total)
+        })
+        .take_while(|&total| total <= amount)
+        .count()
+}
+
+/// Counts the ways to fill exactly `amount` with the containers, by the number of containers,
+/// from a table of the ways to fill every smaller volume.
+///
+/// This takes time proportional to the number of containers, times `amount`, times the most
+/// containers that fit in `amount` together.
+fn fill_table(amount: usize, containers: &[i32]) -> Vec<u128> {
+    let most = most_containers(amount as i32, containers);
+    // ways[n][v] counts the ways to fill exactly `v` with `n` of the containers so far.
+    let mut ways = vec![vec![0u128; amount + 1]; most + 1];
+    ways[0][0] = 1;
+    for (i, &size) in containers.iter().enumerate() {
+        let size = size as usize;
+        if size > amount {
+            continue;
+        }
+        // From the most containers down, so every container is only used once.
+        for n in (1..=most.min(i + 1)).rev() {
+            let (fewer, more) = ways.split_at_mut(n);
+            let (fewer, more) = (&fewer[n - 1], &mut more[0]);
+            for v in size..=amount {
+                more[v] = more[v]
+                    .checked_add(fewer[v - size])
+                    .expect("too many ways to count");
             }
-        },
-    )
+        }
+    }
+    let mut counts = vec![0; containers.len() + 1];
+    for (count, row) in counts.iter_mut().zip(ways.iter()) {
+        *count = row[amount];
+    }
+    counts
 }
 
 #[aoc(day17, part1)]
-pub fn part1(input: &[i32]) -> usize {
-    ways_to_fill(150, input).count()
+pub fn part1(input: &[i32]) -> u128 {
+    ways_to_fill(150, input).iter().sum()
 }
 
-fn ways_to_fill_with_minimum_containers(amount: i32, containers: &[i32]) -> usize {
-    let minimum_ways = ways_to_fill(amount, containers)
-        .map(|way| way.len())
-        .min()
-        .unwrap();
+fn ways_to_fill_with_minimum_containers(amount: i32, containers: &[i32]) -> u128 {
     ways_to_fill(amount, containers)
-        .filter(|way| way.len() == minimum_ways)
-        .count()
+        .into_iter()
+        .find(|&ways| ways > 0)
+        .unwrap_or(0)
 }
 
 #[aoc(day17, part2)]
-pub fn part2(input: &[i32]) -> usize {
+pub fn part2(input: &[i32]) -> u128 {
     ways_to_fill_with_minimum_containers(150, input)
 }
 
@@ -51,14 +100,58 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(
-            ways_to_fill(25, &TEST_INPUT).collect::<Vec<_>>(),
-            vec![vec![20, 5], vec![20, 5], vec![15, 10], vec![15, 5, 5],]
-        );
+        assert_eq!(ways_to_fill(25, &TEST_INPUT), vec![0, 0, 3, 1, 0, 0]);
     }
 
     #[test]
     fn test_part2() {
         assert_eq!(ways_to_fill_with_minimum_containers(25, &TEST_INPUT), 3);
     }
+
+    #[test]
+    fn test_ways_to_fill() {
+        let containers = [20, 15, 10, 5, 5, 0, 7, 33, 12, 1, 5, 18];
+        for amount in -1..140 {
+            let expected = count_subsets_by_len(&containers, i64::from(amount));
+            let ways = ways_to_fill(amount, &containers);
+            assert!(ways
+                .iter()
+                .map(|&ways| ways as u64)
+                .eq(expected.iter().cloned()));
+            if amount >= 0 {
+                let ways = fill_table(amount as usize, &containers);
+                assert!(ways.iter().map(|&ways| ways as u64).eq(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_many_containers() {
+        let containers = (1..=100).collect::<Vec<i32>>();
+        let ways = ways_to_fill(2000, &containers);
+        // The 62 smallest containers hold 1953, so no way uses more of them.
+        assert!(ways[62] > 0);
+        assert!(ways[63..].iter().all(|&ways| ways == 0));
+        // The 22 largest containers only hold 1969.
+        assert_eq!(ways[22], 0);
+        assert!(ways[23] > 0);
+        // The total, counted without keeping track of the number of containers.
+        let mut total = vec![0u128; 2001];
+        total[0] = 1;
+        for &size in containers.iter() {
+            for v in (size as usize..=2000).rev() {
+                total[v] += total[v - size as usize];
+            }
+        }
+        assert_eq!(ways.iter().sum::<u128>(), total[2000]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("20\n15\nten"),
+            "3:1: invalid number `ten`: invalid digit found in string"
+        );
+    }
 }
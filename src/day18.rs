@@ -1,4 +1,4 @@
-use crate::util::Vector2D;
+use crate::util::{ParseError, ParseErrorKind, Vector2D};
 
 #[derive(Debug, Clone)]
 pub struct Grid {
@@ -8,18 +8,40 @@ pub struct Grid {
 }
 
 #[aoc_generator(day18)]
-pub fn input_generator(input: &str) -> Grid {
+pub fn input_generator(input: &str) -> Result<Grid, ParseError> {
     let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
+    let width = input.lines().next().map_or(0, |line| line.len());
     let cells = input
         .lines()
-        .map(|line| line.chars().map(|c| c == '#').collect())
-        .collect();
-    Grid {
+        .map(|line| {
+            if line.len() != width {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    ParseErrorKind::Expected {
+                        expected: format!("{} lights", width),
+                        found: Some(format!("{} lights", line.len())),
+                    },
+                ));
+            }
+            line.char_indices()
+                .map(|(i, c)| match c {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(ParseError::unknown(
+                        input,
+                        &line[i..i + c.len_utf8()],
+                        "light",
+                    )),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Grid {
         width,
         height,
         cells,
-    }
+    })
 }
 
 impl Grid {
@@ -113,14 +135,21 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let grid = input_generator(&TEST_INPUT);
+        let grid = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(simulate(grid, 4, false).count_on(), 4);
     }
 
     #[test]
     fn test_part2() {
-        let mut grid = input_generator(&TEST_INPUT);
+        let mut grid = input_generator(&TEST_INPUT).unwrap();
         grid.make_stuck();
         assert_eq!(simulate(grid, 5, true).count_on(), 17);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("#.\n#"), "2:1: expected 2 lights, found 1 lights");
+        assert_eq!(error("#.\n#x"), "2:2: unknown light `x`");
+    }
 }
@@ -1,24 +1,24 @@
 use std::collections::HashSet;
 
+use crate::util::{parse_lines, split_once, ParseError};
+
 pub type Replacements = Vec<(String, String)>;
 pub type Input = (Replacements, String);
 
-fn parse_replacements(input: &str) -> Replacements {
-    input
-        .lines()
-        .map(|line| {
-            let (input, output) = line.split_once(" => ").unwrap();
-            (input.to_string(), output.to_string())
-        })
-        .collect()
+fn parse_replacements(input: &str) -> Result<Replacements, ParseError> {
+    parse_lines(input, |line| {
+        let (input, output) = split_once(line, line, " => ")?;
+        Ok((input.to_string(), output.to_string()))
+    })
 }
 
 #[aoc_generator(day19)]
-pub fn input_generator(input: &str) -> Input {
-    let (replacements, molecule) = input.split_once("\n\n").unwrap();
-    let replacements = parse_replacements(replacements);
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let (replacements, molecule) = split_once(input, input, "\n\n")?;
+    let replacements =
+        parse_replacements(replacements).map_err(|err| err.offset(input, replacements))?;
     let molecule = molecule.to_string();
-    (replacements, molecule)
+    Ok((replacements, molecule))
 }
 
 fn calibrate(replacements: &[(String, String)], molecule: &str) -> usize {
@@ -99,15 +99,28 @@ O => HH"
 
     #[test]
     fn test_part1() {
-        let replacements = parse_replacements(&REPLACEMENTS_1);
+        let replacements = parse_replacements(&REPLACEMENTS_1).unwrap();
         assert_eq!(calibrate(&replacements, "HOH"), 4);
         assert_eq!(calibrate(&replacements, "HOHOHO"), 7);
     }
 
     #[test]
     fn test_part2() {
-        let replacements = parse_replacements(&REPLACEMENTS_2);
+        let replacements = parse_replacements(&REPLACEMENTS_2).unwrap();
         assert_eq!(build_molecule(&replacements, "HOH".to_string()), Some(3));
         assert_eq!(build_molecule(&replacements, "HOHOHO".to_string()), Some(6));
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("H => HO\nH = OH\n\nHOH"),
+            "2:7: expected `=>`, found end of line"
+        );
+        assert_eq!(
+            error("H => HO"),
+            "1:8: expected whitespace, found end of line"
+        );
+    }
 }
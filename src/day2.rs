@@ -1,6 +1,8 @@
-use std::convert::TryInto;
 use std::str::FromStr;
 
+use crate::util::{parse_lines, parse_number, ParseError};
+
+#[derive(Debug)]
 pub struct Present {
     l: u32,
     w: u32,
@@ -8,22 +10,25 @@ pub struct Present {
 }
 
 impl FromStr for Present {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let [l, w, h]: [u32; 3] = s
-            .split('x')
-            .map(|x| x.parse().unwrap())
-            .collect::<Vec<u32>>()
-            .try_into()
-            .unwrap();
-        Ok(Present { l, w, h })
+        let dimensions = s.split('x').collect::<Vec<_>>();
+        let (l, w, h) = match dimensions[..] {
+            [l, w, h] => (l, w, h),
+            _ => return Err(ParseError::expected(s, s, "three dimensions `LxWxH`")),
+        };
+        Ok(Present {
+            l: parse_number(s, l)?,
+            w: parse_number(s, w)?,
+            h: parse_number(s, h)?,
+        })
     }
 }
 
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Present> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Present>, ParseError> {
+    parse_lines(input, str::parse)
 }
 
 impl Present {
@@ -60,17 +65,30 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = input_generator("2x3x4");
+        let input = input_generator("2x3x4").unwrap();
         assert_eq!(part1(&input), 58);
-        let input = input_generator("1x1x10");
+        let input = input_generator("1x1x10").unwrap();
         assert_eq!(part1(&input), 43);
     }
 
     #[test]
     fn test_part2() {
-        let input = input_generator("2x3x4");
+        let input = input_generator("2x3x4").unwrap();
         assert_eq!(part2(&input), 34);
-        let input = input_generator("1x1x10");
+        let input = input_generator("1x1x10").unwrap();
         assert_eq!(part2(&input), 14);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("2x3x4\n1x1"),
+            "2:1: expected three dimensions `LxWxH`, found `1x1`"
+        );
+        assert_eq!(
+            error("2x3xa"),
+            "1:5: invalid number `a`: invalid digit found in string"
+        );
+    }
 }
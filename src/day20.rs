@@ -1,6 +1,8 @@
+use crate::util::{parse_number, ParseError};
+
 #[aoc_generator(day20)]
-pub fn input_generator(input: &str) -> u32 {
-    input.parse().unwrap()
+pub fn input_generator(input: &str) -> Result<u32, ParseError> {
+    parse_number(input, input)
 }
 
 #[aoc(day20, part1)]
@@ -73,4 +75,17 @@ mod tests {
         assert_eq!(part1(&70), 4);
         assert_eq!(part1(&71), 6);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("12a"),
+            "1:1: invalid number `12a`: invalid digit found in string"
+        );
+        assert_eq!(
+            error(""),
+            "1:1: invalid number ``: cannot parse integer from empty string"
+        );
+    }
 }
@@ -0,0 +1,122 @@
+use super::Player;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Winner {
+    Player,
+    Boss,
+}
+
+/// How a fight ended, with the hit points that both sides have left, which is 0 for the loser.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Outcome {
+    pub winner: Winner,
+    pub player_hp: i32,
+    pub boss_hp: i32,
+}
+
+/// Returns the damage every attack of `attacker` deals to `defender`.
+fn damage_per_attack(attacker: &Player, defender: &Player) -> i32 {
+    attacker.damage.saturating_sub(defender.armor).max(1)
+}
+
+/// Returns the number of attacks it takes `attacker` to bring the hit points of `defender` down
+/// to zero. A defender that has no hit points left still takes one attack.
+pub fn attacks_to_kill(attacker: &Player, defender: &Player) -> i32 {
+    if defender.hp <= 0 {
+        1
+    } else {
+        (defender.hp - 1) / damage_per_attack(attacker, defender) + 1
+    }
+}
+
+/// Resolves a fight where the player attacks first, without playing it out turn by turn.
+pub fn resolve(player: &Player, boss: &Player) -> Outcome {
+    let player_attacks = attacks_to_kill(player, boss);
+    let boss_attacks = attacks_to_kill(boss, player);
+    // The player attacks first, so they win if the boss needs at least as many attacks.
+    if player_attacks <= boss_attacks {
+        Outcome {
+            winner: Winner::Player,
+            player_hp: player.hp - (player_attacks - 1) * damage_per_attack(boss, player),
+            boss_hp: 0,
+        }
+    } else {
+        Outcome {
+            winner: Winner::Boss,
+            player_hp: 0,
+            boss_hp: boss.hp - boss_attacks * damage_per_attack(player, boss),
+        }
+    }
+}
+
+/// Plays out a fight where the player attacks first, turn by turn.
+pub fn simulate(player: &Player, boss: &Player) -> Outcome {
+    let mut player = player.clone();
+    let mut boss = boss.clone();
+    loop {
+        player.attack(&mut boss);
+        if boss.hp <= 0 {
+            return Outcome {
+                winner: Winner::Player,
+                player_hp: player.hp,
+                boss_hp: 0,
+            };
+        }
+        boss.attack(&mut player);
+        if player.hp <= 0 {
+            return Outcome {
+                winner: Winner::Boss,
+                player_hp: 0,
+                boss_hp: boss.hp,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(hp: i32, damage: i32, armor: i32) -> Player {
+        Player { hp, damage, armor }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let outcome = resolve(&player(8, 5, 5), &player(12, 7, 2));
+        assert_eq!(
+            outcome,
+            Outcome {
+                winner: Winner::Player,
+                player_hp: 2,
+                boss_hp: 0,
+            }
+        );
+        assert_eq!(attacks_to_kill(&player(8, 5, 5), &player(12, 7, 2)), 4);
+        assert_eq!(attacks_to_kill(&player(12, 7, 2), &player(8, 5, 5)), 4);
+
+        // Stats far too large to simulate.
+        let outcome = resolve(&player(1_999_999_998, 3, 1), &player(1_999_999_999, 4, 0));
+        assert_eq!(outcome.winner, Winner::Boss);
+        assert_eq!(outcome.boss_hp, 1);
+    }
+
+    #[test]
+    fn test_resolve_matches_simulation() {
+        let stats =
+            |hp| (0..6).flat_map(move |damage| (0..4).map(move |armor| player(hp, damage, armor)));
+        for hp in -1..15 {
+            for player in stats(hp) {
+                for boss in (1..15).flat_map(stats) {
+                    assert_eq!(
+                        resolve(&player, &boss),
+                        simulate(&player, &boss),
+                        "{:?} against {:?}",
+                        player,
+                        boss
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::str::FromStr;
+
+use crate::util::{end_of, parse_number, strip_prefix, ParseError};
+
+pub use self::combat::*;
+pub use self::pareto::*;
+pub use self::random::*;
+pub use self::shop::*;
+pub use self::slots::*;
+
+mod combat;
+mod pareto;
+mod random;
+mod shop;
+mod slots;
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub hp: i32,
+    pub damage: i32,
+    pub armor: i32,
+}
+
+impl FromStr for Player {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let mut next_line = || lines.next().unwrap_or_else(|| end_of(s));
+        let hp = strip_prefix(s, next_line(), "Hit Points: ")?;
+        let damage = strip_prefix(s, next_line(), "Damage: ")?;
+        let armor = strip_prefix(s, next_line(), "Armor: ")?;
+        let hp = parse_number(s, hp)?;
+        let damage = parse_number(s, damage)?;
+        let armor = parse_number(s, armor)?;
+        Ok(Player { hp, damage, armor })
+    }
+}
+
+#[aoc_generator(day21)]
+pub fn input_generator(input: &str) -> Result<Player, ParseError> {
+    input.parse()
+}
+
+fn loadouts(shop: &Shop) -> impl Iterator<Item = Vec<Item>> {
+    loadouts_with(shop, &SlotRules::default())
+}
+
+impl Player {
+    fn attack(&self, other: &mut Player) {
+        other.hp -= (self.damage - other.armor).max(1);
+    }
+
+    fn equip(&mut self, item: Item) {
+        self.damage += item.damage;
+        self.armor += item.armor;
+    }
+}
+
+fn player_wins(player: Player, boss: Player) -> bool {
+    resolve(&player, &boss).winner == Winner::Player
+}
+
+fn loadout_cost(loadout: &[Item]) -> u32 {
+    loadout.iter().map(|item| item.cost).sum()
+}
+
+#[aoc(day21, part1)]
+pub fn part1(boss: &Player) -> u32 {
+    let player = Player {
+        hp: 100,
+        damage: 0,
+        armor: 0,
+    };
+    // Find the cheapest loadout that wins
+    let loadout = cheapest_loadout(&Shop::default(), &SlotRules::default(), |loadout| {
+        // Apply item buffs
+        let mut player = player.clone();
+        for item in loadout.iter().cloned() {
+            player.equip(item);
+        }
+        player_wins(player, boss.clone())
+    });
+    loadout_cost(&loadout.expect("cannot win"))
+}
+
+#[aoc(day21, part2)]
+pub fn part2(boss: &Player) -> u32 {
+    let player = Player {
+        hp: 100,
+        damage: 0,
+        armor: 0,
+    };
+    // Find the most expensive loadout that loses
+    let loadout = dearest_loadout(&Shop::default(), &SlotRules::default(), |loadout| {
+        // Apply item buffs
+        let mut player = player.clone();
+        for item in loadout.iter().cloned() {
+            player.equip(item);
+        }
+        !player_wins(player, boss.clone())
+    });
+    loadout_cost(&loadout.expect("cannot lose"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let player = Player {
+            hp: 8,
+            damage: 5,
+            armor: 5,
+        };
+        let boss = Player {
+            hp: 12,
+            damage: 7,
+            armor: 2,
+        };
+        assert!(player_wins(player, boss));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("Hit Points: 100\nDamage: 8"),
+            "2:10: expected `Armor:`, found end of line"
+        );
+        assert_eq!(
+            error("Hit Points: 100\nDamage: 8\nArmor: x"),
+            "3:8: invalid number `x`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter};
+
+use super::{attacks_to_kill, loadout_cost, loadouts, player_wins, Item, Player, Shop};
+
+/// A winning loadout, with the number of turns the player has to spare: how many more attacks
+/// of the boss they could take before losing.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FrontierEntry {
+    pub items: Vec<Item>,
+    pub cost: u32,
+    pub margin: u32,
+}
+
+impl Display for FrontierEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names = self
+            .items
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "{:>4} gold, {:>3} turns to spare: {}",
+            self.cost,
+            self.margin,
+            names.join(", ")
+        )
+    }
+}
+
+/// Returns every winning loadout that no other winning loadout beats on both cost and turns to
+/// spare, from the cheapest to the one with the most turns to spare.
+///
+/// Loadouts with the same cost and turns to spare are all listed.
+pub fn pareto_frontier(shop: &Shop, player: &Player, boss: &Player) -> Vec<FrontierEntry> {
+    let mut winning = loadouts(shop)
+        .filter_map(|items| {
+            let mut player = player.clone();
+            for item in items.iter().cloned() {
+                player.equip(item);
+            }
+            if !player_wins(player.clone(), boss.clone()) {
+                return None;
+            }
+            // The player attacks first, so they win as long as the boss needs as many attacks.
+            let margin = (attacks_to_kill(boss, &player) - attacks_to_kill(&player, boss)) as u32;
+            let cost = loadout_cost(&items);
+            Some(FrontierEntry {
+                items,
+                cost,
+                margin,
+            })
+        })
+        .collect::<Vec<_>>();
+    winning.sort_by_key(|entry| (entry.cost, std::cmp::Reverse(entry.margin)));
+
+    let mut frontier = Vec::<FrontierEntry>::new();
+    for entry in winning {
+        let keep = match frontier.last() {
+            None => true,
+            Some(last) => {
+                entry.margin > last.margin
+                    || (entry.margin == last.margin && entry.cost == last.cost)
+            }
+        };
+        if keep {
+            frontier.push(entry);
+        }
+    }
+    frontier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::part1;
+    use super::*;
+
+    #[test]
+    fn test_pareto_frontier() {
+        let player = Player {
+            hp: 100,
+            damage: 0,
+            armor: 0,
+        };
+        let boss = Player {
+            hp: 104,
+            damage: 8,
+            armor: 1,
+        };
+        let frontier = pareto_frontier(&Shop::default(), &player, &boss);
+        assert_eq!(frontier[0].cost, part1(&boss));
+        for pair in frontier.windows(2) {
+            assert!(pair[0].cost <= pair[1].cost);
+            assert!(pair[0].margin <= pair[1].margin);
+        }
+        assert_eq!(
+            frontier[0].to_string(),
+            "  78 gold,   0 turns to spare: Longsword, Leather, Damage +1"
+        );
+        assert_eq!(frontier.len(), 16);
+        assert_eq!(
+            frontier[15].to_string(),
+            " 316 gold,  89 turns to spare: Greataxe, Platemail, Damage +3, Defense +2"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::util::Rng;
+
+use super::{cheapest_loadout, Item, Player, Shop, SlotRules};
+
+/// A combat model where the damage of every attack is rolled, and can be a critical hit.
+///
+/// An attack rolls a damage uniformly between `damage - spread` and `damage + spread`, which a
+/// critical hit then multiplies by `crit_multiplier`. Armor is subtracted from the result, and
+/// every attack still deals at least 1 damage.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RandomCombat {
+    spread: i32,
+    crit_chance: f64,
+    crit_multiplier: i32,
+}
+
+/// Why a [`RandomCombat`] cannot be created.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InvalidCombat {
+    NegativeSpread(i32),
+    CritChance(f64),
+    CritMultiplier(i32),
+}
+
+impl Display for InvalidCombat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidCombat::NegativeSpread(spread) => {
+                write!(f, "damage spread {} is negative", spread)
+            }
+            InvalidCombat::CritChance(chance) => {
+                write!(f, "critical hit chance {} is not between 0 and 1", chance)
+            }
+            InvalidCombat::CritMultiplier(multiplier) => {
+                write!(f, "critical hit multiplier {} is less than 1", multiplier)
+            }
+        }
+    }
+}
+
+impl Error for InvalidCombat {}
+
+impl RandomCombat {
+    /// Creates a combat model, where `crit_chance` is the chance that an attack is a critical
+    /// hit, between 0 and 1, and `crit_multiplier` is at least 1.
+    pub fn new(spread: i32, crit_chance: f64, crit_multiplier: i32) -> Result<Self, InvalidCombat> {
+        if spread < 0 {
+            return Err(InvalidCombat::NegativeSpread(spread));
+        }
+        if !(0.0..=1.0).contains(&crit_chance) {
+            return Err(InvalidCombat::CritChance(crit_chance));
+        }
+        if crit_multiplier < 1 {
+            return Err(InvalidCombat::CritMultiplier(crit_multiplier));
+        }
+        Ok(RandomCombat {
+            spread,
+            crit_chance,
+            crit_multiplier,
+        })
+    }
+
+    /// Returns the damage that an attack of `attacker` can deal to `defender`, with the chance
+    /// of dealing it.
+    fn hits(&self, attacker: &Player, defender: &Player) -> Vec<(i32, f64)> {
+        let rolls = 2 * self.spread + 1;
+        let chance = 1.0 / rolls as f64;
+        let mut hits = Vec::<(i32, f64)>::new();
+        for roll in attacker.damage - self.spread..=attacker.damage + self.spread {
+            let outcomes = [
+                (roll, 1.0 - self.crit_chance),
+                (roll * self.crit_multiplier, self.crit_chance),
+            ];
+            for &(damage, p) in outcomes.iter() {
+                let damage = (damage - defender.armor).max(1);
+                match hits.iter_mut().find(|(d, _)| *d == damage) {
+                    Some(hit) => hit.1 += p * chance,
+                    None => hits.push((damage, p * chance)),
+                }
+            }
+        }
+        hits.retain(|&(_, p)| p > 0.0);
+        hits
+    }
+
+    fn roll(&self, attacker: &Player, defender: &Player, rng: &mut Rng) -> i32 {
+        let rolls = 2 * self.spread + 1;
+        let mut damage = attacker.damage - self.spread + rng.below(rolls as u64) as i32;
+        if rng.next_f64() < self.crit_chance {
+            damage *= self.crit_multiplier;
+        }
+        (damage - defender.armor).max(1)
+    }
+
+    /// Returns the exact chance that the player wins a fight where they attack first.
+    ///
+    /// This takes time and memory proportional to the product of the hit points of both sides.
+    pub fn win_probability(&self, player: &Player, boss: &Player) -> f64 {
+        let player_hits = self.hits(player, boss);
+        let boss_hits = self.hits(boss, player);
+        let rows = player.hp.max(0) as usize + 1;
+        let columns = boss.hp.max(0) as usize + 1;
+        // The chance that the player wins with `p` and `b` hit points left, when it's the turn
+        // of the player, and when it's the turn of the boss.
+        let mut player_turn = vec![vec![0.0; columns]; rows];
+        let mut boss_turn = vec![vec![0.0; columns]; rows];
+        let attack = |player_turn: &[Vec<f64>], b: usize, p: usize| -> f64 {
+            boss_hits
+                .iter()
+                .filter(|&&(damage, _)| damage < p as i32)
+                .map(|&(damage, chance)| chance * player_turn[p - damage as usize][b])
+                .sum()
+        };
+        let defend = |boss_turn: &[Vec<f64>], b: i32, p: usize| -> f64 {
+            player_hits
+                .iter()
+                .map(|&(damage, chance)| {
+                    if damage >= b {
+                        chance
+                    } else {
+                        chance * boss_turn[p][(b - damage) as usize]
+                    }
+                })
+                .sum()
+        };
+        for p in 1..rows {
+            for b in 1..columns {
+                boss_turn[p][b] = attack(&player_turn, b, p);
+                player_turn[p][b] = defend(&boss_turn, b as i32, p);
+            }
+        }
+        if player.hp <= 0 {
+            // The player only wins if their first attack kills the boss.
+            defend(&boss_turn, boss.hp, 0)
+        } else {
+            defend(&boss_turn, boss.hp, player.hp as usize)
+        }
+    }
+
+    /// Estimates the chance that the player wins a fight where they attack first, by playing
+    /// out `fights` fights with random numbers from `seed`.
+    pub fn estimate_win_probability(
+        &self,
+        player: &Player,
+        boss: &Player,
+        fights: u32,
+        seed: u64,
+    ) -> f64 {
+        let mut rng = Rng::new(seed);
+        let mut wins = 0;
+        for _ in 0..fights {
+            let mut player_hp = player.hp;
+            let mut boss_hp = boss.hp;
+            loop {
+                boss_hp -= self.roll(player, boss, &mut rng);
+                if boss_hp <= 0 {
+                    wins += 1;
+                    break;
+                }
+                player_hp -= self.roll(boss, player, &mut rng);
+                if player_hp <= 0 {
+                    break;
+                }
+            }
+        }
+        f64::from(wins) / f64::from(fights)
+    }
+}
+
+/// Finds the cheapest loadout that wins with at least the given chance, with its exact chance
+/// of winning.
+pub fn cheapest_likely_win(
+    shop: &Shop,
+    player: &Player,
+    boss: &Player,
+    combat: &RandomCombat,
+    probability: f64,
+) -> Option<(Vec<Item>, f64)> {
+    let win_probability = |loadout: &[Item]| {
+        let mut player = player.clone();
+        for item in loadout.iter().cloned() {
+            player.equip(item);
+        }
+        combat.win_probability(&player, boss)
+    };
+    let loadout = cheapest_loadout(shop, &SlotRules::default(), |loadout| {
+        win_probability(loadout) >= probability
+    })?;
+    let chance = win_probability(&loadout);
+    Some((loadout, chance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{loadout_cost, loadouts, resolve, Winner};
+    use super::*;
+
+    fn player(hp: i32, damage: i32, armor: i32) -> Player {
+        Player { hp, damage, armor }
+    }
+
+    fn random_combat() -> RandomCombat {
+        RandomCombat::new(2, 0.1, 2).unwrap()
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let combat = RandomCombat::new(0, 0.0, 2).unwrap();
+        for hp in 0..10 {
+            for damage in 0..5 {
+                let player = player(hp, damage, 1);
+                for boss in (1..10).map(|hp| self::player(hp, 3, 2)) {
+                    let wins = resolve(&player, &boss).winner == Winner::Player;
+                    let expected = if wins { 1.0 } else { 0.0 };
+                    assert_eq!(combat.win_probability(&player, &boss), expected);
+                    assert_eq!(
+                        combat.estimate_win_probability(&player, &boss, 10, 1),
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_win_probability() {
+        // A single attack of 1 to 3 damage, against 2 hit points.
+        let combat = RandomCombat::new(1, 0.0, 1).unwrap();
+        let chance = combat.win_probability(&player(1, 2, 0), &player(2, 5, 0));
+        assert!((chance - 2.0 / 3.0).abs() < 1e-12);
+
+        let player = self::player(30, 6, 2);
+        let boss = self::player(35, 7, 1);
+        let exact = random_combat().win_probability(&player, &boss);
+        let estimate = random_combat().estimate_win_probability(&player, &boss, 20_000, 7);
+        assert!((exact - estimate).abs() < 0.02, "{} vs {}", exact, estimate);
+        assert_eq!(
+            estimate,
+            random_combat().estimate_win_probability(&player, &boss, 20_000, 7)
+        );
+    }
+
+    #[test]
+    fn test_cheapest_likely_win() {
+        let shop = Shop::default();
+        let player = player(20, 0, 0);
+        let boss = self::player(25, 6, 1);
+        let (loadout, chance) =
+            cheapest_likely_win(&shop, &player, &boss, &random_combat(), 0.95).unwrap();
+        assert!(chance >= 0.95);
+        let cost = loadout_cost(&loadout);
+        for cheaper in loadouts(&shop).filter(|loadout| loadout_cost(loadout) < cost) {
+            let mut player = player.clone();
+            for item in cheaper {
+                player.equip(item);
+            }
+            assert!(random_combat().win_probability(&player, &boss) < 0.95);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            RandomCombat::new(-1, 0.1, 2),
+            Err(InvalidCombat::NegativeSpread(-1))
+        );
+        assert_eq!(
+            RandomCombat::new(2, 1.5, 2),
+            Err(InvalidCombat::CritChance(1.5))
+        );
+        assert!(RandomCombat::new(2, f64::NAN, 2).is_err());
+        assert_eq!(
+            RandomCombat::new(2, 0.1, 0),
+            Err(InvalidCombat::CritMultiplier(0))
+        );
+        assert!(RandomCombat::new(2, 0.1, 1).is_ok());
+        assert_eq!(
+            InvalidCombat::CritChance(-0.5).to_string(),
+            "critical hit chance -0.5 is not between 0 and 1"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{end_of, parse_number, split_once, ParseError};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Item {
+    pub name: String,
+    pub cost: u32,
+    pub damage: i32,
+    pub armor: i32,
+}
+
+/// The items for sale, by category.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Shop {
+    pub weapons: Vec<Item>,
+    pub armor: Vec<Item>,
+    pub rings: Vec<Item>,
+}
+
+/// The shop from the puzzle.
+impl Default for Shop {
+    fn default() -> Self {
+        include_str!("shop.txt").parse().unwrap()
+    }
+}
+
+/// Parses a line like `Damage +1    25     1       0`: a name, followed by the cost, damage
+/// and armor of the item.
+fn parse_item(s: &str) -> Result<Item, ParseError> {
+    let mut rest = s.trim_end();
+    let mut numbers = [""; 3];
+    for number in numbers.iter_mut().rev() {
+        let (before, token) = rest
+            .rsplit_once(' ')
+            .ok_or_else(|| ParseError::expected(s, rest, "item name"))?;
+        *number = token;
+        rest = before.trim_end();
+    }
+    if rest.is_empty() {
+        return Err(ParseError::expected(s, s, "item name"));
+    }
+    Ok(Item {
+        name: rest.to_string(),
+        cost: parse_number(s, numbers[0])?,
+        damage: parse_number(s, numbers[1])?,
+        armor: parse_number(s, numbers[2])?,
+    })
+}
+
+/// Parses the shop in the format of the puzzle: a header line like `Weapons:    Cost  Damage
+/// Armor` for every category, followed by its items, with blank lines in between.
+impl FromStr for Shop {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut shop = Shop {
+            weapons: vec![],
+            armor: vec![],
+            rings: vec![],
+        };
+        // The items of the category that the current block of lines lists.
+        let mut items: Option<&mut Vec<Item>> = None;
+        for line in s.lines() {
+            if line.trim().is_empty() {
+                items = None;
+                continue;
+            }
+            match items.as_mut() {
+                Some(items) => items.push(parse_item(line).map_err(|err| err.offset(s, line))?),
+                None => {
+                    let (name, columns) = split_once(s, line, ":")?;
+                    let mut columns = columns.split_whitespace();
+                    for &expected in ["Cost", "Damage", "Armor"].iter() {
+                        match columns.next() {
+                            Some(column) if column == expected => {}
+                            column => {
+                                let at = column.unwrap_or_else(|| end_of(line));
+                                let expected = format!("`{}`", expected);
+                                return Err(ParseError::expected(s, at, expected));
+                            }
+                        }
+                    }
+                    items = Some(match name {
+                        "Weapons" => &mut shop.weapons,
+                        "Armor" => &mut shop.armor,
+                        "Rings" => &mut shop.rings,
+                        _ => return Err(ParseError::unknown(s, name, "shop category")),
+                    });
+                }
+            }
+        }
+        Ok(shop)
+    }
+}
+
+/// Displays the shop in the format of the puzzle.
+impl Display for Shop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let categories = [
+            ("Weapons", &self.weapons),
+            ("Armor", &self.armor),
+            ("Rings", &self.rings),
+        ];
+        for (i, (name, items)) in categories.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{:<12}Cost  Damage  Armor", format!("{}:", name))?;
+            for item in items.iter() {
+                writeln!(
+                    f,
+                    "{:<11}{:>4}{:>6}{:>8}",
+                    item.name, item.cost, item.damage, item.armor
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let shop = Shop::default();
+        assert_eq!(shop.weapons.len(), 5);
+        assert_eq!(shop.armor.len(), 5);
+        assert_eq!(
+            shop.rings[1],
+            Item {
+                name: "Damage +2".to_string(),
+                cost: 50,
+                damage: 2,
+                armor: 0,
+            }
+        );
+        assert_eq!(shop.to_string(), include_str!("shop.txt"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| s.parse::<Shop>().unwrap_err().to_string();
+        assert_eq!(
+            error("Shields:    Cost  Damage  Armor\nBuckler 10 0 1"),
+            "1:1: unknown shop category `Shields`"
+        );
+        assert_eq!(
+            error("Weapons:    Cost  Armor"),
+            "1:19: expected `Damage`, found `Armor`"
+        );
+        assert_eq!(
+            error("Rings:    Cost  Damage  Armor\nDamage +1  25  one  0"),
+            "2:16: invalid number `one`: invalid digit found in string"
+        );
+        assert_eq!(
+            error("Rings:    Cost  Damage  Armor\n  25  1  0"),
+            "2:1: expected item name, found `25`"
+        );
+    }
+}
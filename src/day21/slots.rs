@@ -0,0 +1,246 @@
+use itertools::Itertools;
+
+use super::{Item, Shop};
+
+/// How many items of one category a loadout can hold.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SlotRule {
+    pub min: usize,
+    pub max: usize,
+    /// Whether the shop only has one of every item, so that no item can be bought twice.
+    pub unique: bool,
+}
+
+/// The slot rules for every category of the shop.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SlotRules {
+    pub weapons: SlotRule,
+    pub armor: SlotRule,
+    pub rings: SlotRule,
+}
+
+/// The rules of the puzzle: exactly one weapon, at most one armor, and at most two different
+/// rings.
+impl Default for SlotRules {
+    fn default() -> Self {
+        SlotRules {
+            weapons: SlotRule {
+                min: 1,
+                max: 1,
+                unique: true,
+            },
+            armor: SlotRule {
+                min: 0,
+                max: 1,
+                unique: true,
+            },
+            rings: SlotRule {
+                min: 0,
+                max: 2,
+                unique: true,
+            },
+        }
+    }
+}
+
+impl SlotRules {
+    fn categories<'a>(&self, shop: &'a Shop) -> [(&'a [Item], SlotRule); 3] {
+        [
+            (&shop.weapons, self.weapons),
+            (&shop.armor, self.armor),
+            (&shop.rings, self.rings),
+        ]
+    }
+}
+
+impl SlotRule {
+    /// Returns every way to fill this slot from `items`, from the fewest items to the most.
+    fn choices(self, items: &[Item]) -> Vec<Vec<Item>> {
+        let mut choices = vec![];
+        for count in self.min..=self.max {
+            if self.unique {
+                choices.extend(items.iter().cloned().combinations(count));
+            } else {
+                choices.extend(items.iter().cloned().combinations_with_replacement(count));
+            }
+        }
+        choices
+    }
+}
+
+/// Returns every loadout that follows the rules, with its items ordered by category.
+pub fn loadouts_with(shop: &Shop, rules: &SlotRules) -> impl Iterator<Item = Vec<Item>> {
+    rules
+        .categories(shop)
+        .iter()
+        .map(|&(items, rule)| rule.choices(items))
+        .multi_cartesian_product()
+        .map(|choices| choices.concat())
+}
+
+/// A depth-first search for the best loadout, choosing the items of one category at a time.
+struct Search<'a, A> {
+    /// The choices of every category, with their keys, sorted by key.
+    categories: &'a [Vec<(i64, Vec<Item>)>],
+    /// The lowest key that the categories from every index onwards can add.
+    rest: &'a [i64],
+    accept: A,
+    loadout: Vec<Item>,
+    best: Option<(i64, Vec<Item>)>,
+}
+
+impl<'a, A: FnMut(&[Item]) -> bool> Search<'a, A> {
+    fn visit(&mut self, category: usize, key: i64) {
+        if category == self.categories.len() {
+            if (self.accept)(&self.loadout) {
+                self.best = Some((key, self.loadout.clone()));
+            }
+            return;
+        }
+        let categories = self.categories;
+        for (choice_key, choice) in categories[category].iter() {
+            let key = key + choice_key;
+            if let Some((best, _)) = self.best {
+                // The choices are sorted, so none of the later ones can do better either.
+                if key + self.rest[category + 1] >= best {
+                    break;
+                }
+            }
+            let len = self.loadout.len();
+            self.loadout.extend(choice.iter().cloned());
+            self.visit(category + 1, key);
+            self.loadout.truncate(len);
+        }
+    }
+}
+
+/// Searches the loadouts that follow the rules for the one with the lowest `key` that is
+/// accepted. The key of a loadout is the sum of the keys of its items.
+///
+/// Rather than trying every loadout, this tries the choices of every category in order of their
+/// key, and skips all loadouts that cannot beat the best one found so far.
+fn best_loadout<K, A>(shop: &Shop, rules: &SlotRules, key: K, accept: A) -> Option<Vec<Item>>
+where
+    K: Fn(&Item) -> i64,
+    A: FnMut(&[Item]) -> bool,
+{
+    let categories = rules
+        .categories(shop)
+        .iter()
+        .map(|&(items, rule)| {
+            let mut choices = rule
+                .choices(items)
+                .into_iter()
+                .map(|choice| (choice.iter().map(&key).sum::<i64>(), choice))
+                .collect::<Vec<_>>();
+            choices.sort_by_key(|&(key, _)| key);
+            choices
+        })
+        .collect::<Vec<_>>();
+    if categories.iter().any(|choices| choices.is_empty()) {
+        return None;
+    }
+    let mut rest = vec![0; categories.len() + 1];
+    for i in (0..categories.len()).rev() {
+        rest[i] = rest[i + 1] + categories[i][0].0;
+    }
+
+    let mut search = Search {
+        categories: &categories,
+        rest: &rest,
+        accept,
+        loadout: vec![],
+        best: None,
+    };
+    search.visit(0, 0);
+    search.best.map(|(_, loadout)| loadout)
+}
+
+/// Finds the cheapest loadout that follows the rules and is accepted.
+pub fn cheapest_loadout<A>(shop: &Shop, rules: &SlotRules, accept: A) -> Option<Vec<Item>>
+where
+    A: FnMut(&[Item]) -> bool,
+{
+    best_loadout(shop, rules, |item| i64::from(item.cost), accept)
+}
+
+/// Finds the most expensive loadout that follows the rules and is accepted.
+pub fn dearest_loadout<A>(shop: &Shop, rules: &SlotRules, accept: A) -> Option<Vec<Item>>
+where
+    A: FnMut(&[Item]) -> bool,
+{
+    best_loadout(shop, rules, |item| -i64::from(item.cost), accept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::loadout_cost;
+    use super::*;
+
+    #[test]
+    fn test_loadouts_with() {
+        let shop = Shop::default();
+        let rules = SlotRules::default();
+        assert_eq!(loadouts_with(&shop, &rules).count(), 5 * 6 * 22);
+
+        // Up to two weapons, which can be the same, and exactly three different rings.
+        let rules = SlotRules {
+            weapons: SlotRule {
+                min: 1,
+                max: 2,
+                unique: false,
+            },
+            armor: SlotRule {
+                min: 0,
+                max: 0,
+                unique: true,
+            },
+            rings: SlotRule {
+                min: 3,
+                max: 3,
+                unique: true,
+            },
+        };
+        assert_eq!(loadouts_with(&shop, &rules).count(), (5 + 15) * 20);
+        let rules = SlotRules {
+            rings: SlotRule {
+                min: 7,
+                max: 7,
+                unique: true,
+            },
+            ..rules
+        };
+        assert_eq!(loadouts_with(&shop, &rules).count(), 0);
+        assert_eq!(cheapest_loadout(&shop, &rules, |_| true), None);
+    }
+
+    #[test]
+    fn test_best_loadout() {
+        let shop = Shop::default();
+        let rules = SlotRules::default();
+        for threshold in (0..400).step_by(7) {
+            let accept = |loadout: &[Item]| {
+                let damage: i32 = loadout.iter().map(|item| item.damage).sum();
+                let armor: i32 = loadout.iter().map(|item| item.armor).sum();
+                damage * 50 + armor * 30 >= threshold
+            };
+            let accepted = loadouts_with(&shop, &rules)
+                .filter(|loadout| accept(loadout))
+                .map(|loadout| loadout_cost(&loadout))
+                .collect::<Vec<_>>();
+            let cost = |loadout: Option<Vec<Item>>| loadout.map(|loadout| loadout_cost(&loadout));
+            assert_eq!(
+                cost(cheapest_loadout(&shop, &rules, accept)),
+                accepted.iter().min().cloned()
+            );
+            let reject = |loadout: &[Item]| !accept(loadout);
+            assert_eq!(
+                cost(dearest_loadout(&shop, &rules, reject)),
+                loadouts_with(&shop, &rules)
+                    .filter(|loadout| reject(loadout))
+                    .map(|loadout| loadout_cost(&loadout))
+                    .max()
+            );
+        }
+    }
+}
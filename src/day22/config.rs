@@ -0,0 +1,73 @@
+use std::fmt::{Display, Formatter};
+
+use super::{Player, Side};
+
+/// The rules of a battle, apart from the spells and the boss.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BattleConfig {
+    /// The hit points and mana that the player starts with.
+    pub player: Player,
+    /// The hit points that the player loses at the start of each of their turns, before any
+    /// effects apply.
+    pub drain: u32,
+    /// The armor of the boss, which reduces the damage of every spell and effect.
+    pub boss_armor: u32,
+    /// Who takes the first turn.
+    pub first: Side,
+}
+
+impl BattleConfig {
+    /// The rules of part 2, where the player loses a hit point every turn.
+    pub fn hard() -> Self {
+        Self {
+            drain: 1,
+            ..Self::default()
+        }
+    }
+}
+
+/// The rules of part 1.
+impl Default for BattleConfig {
+    fn default() -> Self {
+        Self {
+            player: Player { hp: 50, mana: 500 },
+            drain: 0,
+            boss_armor: 0,
+            first: Side::Player,
+        }
+    }
+}
+
+/// A number in a [`BattleConfig`] that can be swept over.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Parameter {
+    PlayerHp,
+    PlayerMana,
+    Drain,
+    BossArmor,
+}
+
+impl Parameter {
+    /// Returns a copy of the config with this parameter set to `value`.
+    pub fn apply(self, config: &BattleConfig, value: u32) -> BattleConfig {
+        let mut config = config.clone();
+        match self {
+            Parameter::PlayerHp => config.player.hp = value,
+            Parameter::PlayerMana => config.player.mana = value,
+            Parameter::Drain => config.drain = value,
+            Parameter::BossArmor => config.boss_armor = value,
+        }
+        config
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Parameter::PlayerHp => write!(f, "player hit points"),
+            Parameter::PlayerMana => write!(f, "player mana"),
+            Parameter::Drain => write!(f, "drain"),
+            Parameter::BossArmor => write!(f, "boss armor"),
+        }
+    }
+}
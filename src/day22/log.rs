@@ -0,0 +1,191 @@
+use std::fmt::{Display, Formatter};
+
+use super::Spell;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Side {
+    Player,
+    Boss,
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Player => write!(f, "Player"),
+            Side::Boss => write!(f, "Boss"),
+        }
+    }
+}
+
+/// Something that happened during a turn.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Event {
+    /// The player lost this many hit points at the start of their turn.
+    Drained(u32),
+    /// The active effect of a spell was applied, and now has `timer` turns left.
+    Effect {
+        spell: Spell,
+        timer: u32,
+    },
+    WoreOff(Spell),
+    Cast(Spell),
+    /// The boss attacked for `damage`, while the player had `armor`.
+    Attack {
+        damage: u32,
+        armor: u32,
+    },
+    /// The previous event brought the hit points of this side down to zero.
+    Killed(Side),
+}
+
+/// The state at the start of a turn, and what happened during it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Turn {
+    pub side: Side,
+    pub player_hp: u32,
+    pub armor: u32,
+    pub mana: u32,
+    pub boss_hp: u32,
+    pub boss_armor: u32,
+    pub events: Vec<Event>,
+}
+
+fn hit_points(hp: u32) -> &'static str {
+    if hp == 1 {
+        "hit point"
+    } else {
+        "hit points"
+    }
+}
+
+/// Describes the damage dealt to something with `armor`, which always deals at least 1.
+fn damage(damage: u32, armor: u32) -> String {
+    if armor == 0 {
+        damage.to_string()
+    } else {
+        let dealt = damage.saturating_sub(armor).max(1);
+        format!("{} - {} = {}", damage, armor, dealt)
+    }
+}
+
+/// Displays the turn in the narrative format of the puzzle.
+impl Display for Turn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "-- {} turn --", self.side)?;
+        writeln!(
+            f,
+            "- Player has {} {}, {} armor, {} mana",
+            self.player_hp,
+            hit_points(self.player_hp),
+            self.armor,
+            self.mana
+        )?;
+        write!(
+            f,
+            "- Boss has {} {}",
+            self.boss_hp,
+            hit_points(self.boss_hp)
+        )?;
+        if self.boss_armor > 0 {
+            write!(f, ", {} armor", self.boss_armor)?;
+        }
+        writeln!(f)?;
+        let mut events = self.events.iter().peekable();
+        while let Some(event) = events.next() {
+            // The sentence about a killing blow continues the line of the event that dealt it.
+            let killing = matches!(events.peek(), Some(Event::Killed(_)));
+            match event {
+                &Event::Drained(hp) => write!(f, "Player loses {} {}.", hp, hit_points(hp))?,
+                Event::Effect { spell, timer } => {
+                    let effect = spell.effect.as_ref().unwrap();
+                    let mut parts = Vec::new();
+                    if effect.damage > 0 {
+                        let damage = damage(effect.damage, self.boss_armor);
+                        parts.push(format!("deals {} damage", damage));
+                    }
+                    if effect.mana > 0 {
+                        parts.push(format!("provides {} mana", effect.mana));
+                    }
+                    if parts.is_empty() {
+                        write!(f, "{}'s timer is now {}.", spell.name, timer)?
+                    } else if killing {
+                        write!(f, "{} {}.", spell.name, parts.join(" and "))?
+                    } else {
+                        let parts = parts.join(" and ");
+                        write!(f, "{} {}; its timer is now {}.", spell.name, parts, timer)?
+                    }
+                }
+                Event::WoreOff(spell) => match &spell.effect {
+                    Some(effect) if effect.armor > 0 => write!(
+                        f,
+                        "{} wears off, decreasing armor by {}.",
+                        spell.name, effect.armor
+                    )?,
+                    _ => write!(f, "{} wears off.", spell.name)?,
+                },
+                Event::Cast(spell) => {
+                    write!(f, "Player casts {}", spell.name)?;
+                    let mut parts = Vec::new();
+                    if spell.damage > 0 {
+                        let damage = damage(spell.damage, self.boss_armor);
+                        parts.push(format!("dealing {} damage", damage));
+                    }
+                    if spell.heal > 0 {
+                        let heal = format!("healing {} {}", spell.heal, hit_points(spell.heal));
+                        parts.push(heal);
+                    }
+                    match &spell.effect {
+                        Some(effect) if effect.armor > 0 => {
+                            parts.push(format!("increasing armor by {}", effect.armor))
+                        }
+                        _ => {}
+                    }
+                    for (i, part) in parts.iter().enumerate() {
+                        let and = if i > 0 { "and " } else { "" };
+                        write!(f, ", {}{}", and, part)?;
+                    }
+                    write!(f, ".")?;
+                }
+                &Event::Attack {
+                    damage: attack,
+                    armor,
+                } => write!(f, "Boss attacks for {} damage.", damage(attack, armor))?,
+                Event::Killed(Side::Boss) => {
+                    write!(f, " This kills the boss, and the player wins.")?
+                }
+                Event::Killed(Side::Player) => {
+                    write!(f, " This kills the player, and the boss wins.")?
+                }
+            }
+            if !killing {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A battle played out turn by turn.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Battle {
+    /// The names of the spells cast by the player, in order.
+    pub spells: Vec<String>,
+    /// The total mana spent on the spells.
+    pub cost: u32,
+    /// Who won, if the battle got that far.
+    pub winner: Option<Side>,
+    pub turns: Vec<Turn>,
+}
+
+/// Displays the battle in the narrative format of the puzzle, with a blank line between turns.
+impl Display for Battle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, turn) in self.turns.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", turn)?;
+        }
+        Ok(())
+    }
+}
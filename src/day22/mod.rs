@@ -0,0 +1,789 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{end_of, parse_number, strip_prefix, ParseError};
+
+pub use self::config::*;
+pub use self::log::*;
+pub use self::spell::*;
+
+mod config;
+mod log;
+mod spell;
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Boss {
+    hp: u32,
+    damage: u32,
+}
+
+impl FromStr for Boss {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let mut next_line = || lines.next().unwrap_or_else(|| end_of(s));
+        let hp = strip_prefix(s, next_line(), "Hit Points: ")?;
+        let damage = strip_prefix(s, next_line(), "Damage: ")?;
+        let hp = parse_number(s, hp)?;
+        let damage = parse_number(s, damage)?;
+        Ok(Boss { hp, damage })
+    }
+}
+
+#[aoc_generator(day22)]
+pub fn input_generator(input: &str) -> Result<Boss, ParseError> {
+    input.parse()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Player {
+    pub hp: u32,
+    pub mana: u32,
+}
+
+/// The damage a spell or effect deals to a boss with `armor`, which is at least 1 if it deals
+/// any damage at all.
+fn damage_through(damage: u32, armor: u32) -> u32 {
+    if damage > 0 {
+        damage.saturating_sub(armor).max(1)
+    } else {
+        0
+    }
+}
+
+/// The cheapest rate at which spells damage the boss: `cost` mana for `damage` hit points.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct DamageRate {
+    cost: u32,
+    damage: u32,
+}
+
+impl DamageRate {
+    /// Finds the spell with the most damage per mana, counting all the damage of its effect.
+    fn best(spellbook: &Spellbook, armor: u32) -> Option<Self> {
+        spellbook
+            .spells()
+            .iter()
+            .map(|spell| {
+                let effect = spell.effect.as_ref().map_or(0, |effect| {
+                    damage_through(effect.damage, armor) * effect.duration
+                });
+                DamageRate {
+                    cost: spell.cost,
+                    damage: damage_through(spell.damage, armor) + effect,
+                }
+            })
+            .filter(|rate| rate.damage > 0)
+            // Compare `a.cost / a.damage` with `b.cost / b.damage` without rounding.
+            .min_by(|a, b| {
+                let a_cost = u64::from(a.cost) * u64::from(b.damage);
+                let b_cost = u64::from(b.cost) * u64::from(a.damage);
+                a_cost.cmp(&b_cost)
+            })
+    }
+}
+
+/// The part of a game that decides how it can continue, regardless of the spells that led to it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct StateKey {
+    player: Player,
+    boss_hp: u32,
+    effects: Vec<ActiveEffect>,
+}
+
+/// How much work a search did.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SearchStats {
+    /// The number of states taken off the queue and expanded.
+    pub expanded: usize,
+    /// The number of states skipped because an equivalent state was expanded before.
+    pub duplicates: usize,
+    /// The largest number of states waiting in the queue at once.
+    pub max_queue: usize,
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expanded {} states, skipped {} duplicates, queue size at most {}",
+            self.expanded, self.duplicates, self.max_queue
+        )
+    }
+}
+
+/// An effect started by a spell, with the number of turns it stays active.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct ActiveEffect {
+    /// The index of the spell in the spellbook.
+    spell: usize,
+    timer: u32,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Game<'a> {
+    spellbook: &'a Spellbook,
+    config: &'a BattleConfig,
+    player: Player,
+    boss: Boss,
+    effects: Vec<ActiveEffect>,
+    turn: u32,
+    cost: u32,
+    /// A lower bound on the cost of winning from here, including the mana spent so far.
+    bound: u32,
+    /// The indices of the spells cast so far.
+    spells: Vec<usize>,
+    /// The turns played so far, if they are logged.
+    log: Option<Vec<Turn>>,
+}
+
+impl<'a> Game<'a> {
+    fn new(spellbook: &'a Spellbook, config: &'a BattleConfig, boss: Boss) -> Self {
+        Self {
+            spellbook,
+            config,
+            player: config.player.clone(),
+            boss,
+            effects: vec![],
+            turn: 0,
+            cost: 0,
+            bound: 0,
+            spells: vec![],
+            log: None,
+        }
+    }
+
+    /// Logs every turn from now on.
+    fn with_log(mut self) -> Self {
+        self.log = Some(Vec::new());
+        self
+    }
+
+    fn begin_turn(&mut self, side: Side) {
+        let turn = Turn {
+            side,
+            player_hp: self.player.hp,
+            armor: self.player_armor(),
+            mana: self.player.mana,
+            boss_hp: self.boss.hp,
+            boss_armor: self.config.boss_armor,
+            events: vec![],
+        };
+        if let Some(log) = self.log.as_mut() {
+            log.push(turn);
+        }
+    }
+
+    /// Records an event, which is only created if the turns are logged.
+    fn record(&mut self, event: impl FnOnce() -> Event) {
+        if let Some(turn) = self.log.as_mut().and_then(|log| log.last_mut()) {
+            turn.events.push(event());
+        }
+    }
+
+    /// Records who won, if the last event decided the battle.
+    fn winner(&mut self) -> Option<Side> {
+        let winner = if self.player_wins() {
+            Side::Player
+        } else if self.player_loses() {
+            Side::Boss
+        } else {
+            return None;
+        };
+        let loser = match winner {
+            Side::Player => Side::Boss,
+            Side::Boss => Side::Player,
+        };
+        self.record(|| Event::Killed(loser));
+        Some(winner)
+    }
+
+    /// Plays the boss's turn before the player's first turn, if the boss goes first.
+    fn opening_turn(&mut self) -> Option<Side> {
+        match self.config.first {
+            Side::Player => None,
+            Side::Boss => self.boss_turn(),
+        }
+    }
+
+    /// Plays the start of the player's turn, up to casting a spell.
+    fn player_turn(&mut self) -> Option<Side> {
+        self.begin_turn(Side::Player);
+        let drain = self.config.drain;
+        if drain > 0 {
+            // At the start of each player turn (before any other effects apply),
+            // you lose hit points. If this brings you to or below 0 hit points, you lose.
+            self.player.hp = self.player.hp.saturating_sub(drain);
+            self.record(|| Event::Drained(drain));
+            if let Some(winner) = self.winner() {
+                return Some(winner);
+            }
+        }
+        self.start_turn()
+    }
+
+    fn boss_turn(&mut self) -> Option<Side> {
+        // The spell cast in the player's turn may have killed the boss already.
+        if self.player_wins() {
+            return Some(Side::Player);
+        }
+        self.begin_turn(Side::Boss);
+        if let Some(winner) = self.start_turn() {
+            return Some(winner);
+        }
+        self.boss_attack();
+        self.winner()
+    }
+
+    fn into_battle(self, winner: Option<Side>) -> Battle {
+        let spellbook = self.spellbook;
+        Battle {
+            spells: self
+                .spells
+                .iter()
+                .map(|&spell| spellbook.spells()[spell].name.clone())
+                .collect(),
+            cost: self.cost,
+            winner,
+            turns: self.log.unwrap_or_default(),
+        }
+    }
+
+    fn can_cast(&self, index: usize) -> bool {
+        let spell = &self.spellbook.spells()[index];
+        // You must have enough mana to cast a spell.
+        if self.player.mana < spell.cost {
+            return false;
+        }
+        // You cannot cast a spell that would start an effect which is already active.
+        spell.effect.is_none() || self.effects.iter().all(|effect| effect.spell != index)
+    }
+
+    fn cast(&mut self, index: usize) {
+        debug_assert!(self.can_cast(index));
+        let spellbook = self.spellbook;
+        let spell = &spellbook.spells()[index];
+        self.player.mana -= spell.cost;
+        self.cost += spell.cost;
+        self.spells.push(index);
+        self.record(|| Event::Cast(spell.clone()));
+        self.damage_boss(spell.damage);
+        self.heal_player(spell.heal);
+        if let Some(effect) = &spell.effect {
+            if effect.duration > 0 {
+                self.effects.push(ActiveEffect {
+                    spell: index,
+                    timer: effect.duration,
+                });
+                // Keep the effects in the order of the spellbook, and apply them in that order.
+                self.effects.sort();
+            }
+        }
+        if self.player_wins() {
+            self.record(|| Event::Killed(Side::Boss));
+        }
+    }
+
+    fn damage_boss(&mut self, amount: u32) {
+        let amount = damage_through(amount, self.config.boss_armor);
+        self.boss.hp = self.boss.hp.saturating_sub(amount);
+    }
+
+    fn damage_player(&mut self, amount: u32) {
+        let amount = amount.saturating_sub(self.player_armor()).max(1);
+        self.player.hp = self.player.hp.saturating_sub(amount);
+    }
+
+    fn heal_player(&mut self, amount: u32) {
+        self.player.hp += amount;
+    }
+
+    fn effect(&self, index: usize) -> &'a Effect {
+        let spellbook = self.spellbook;
+        spellbook.spells()[index].effect.as_ref().unwrap()
+    }
+
+    fn player_armor(&self) -> u32 {
+        self.effects
+            .iter()
+            .map(|effect| self.effect(effect.spell).armor)
+            .sum()
+    }
+
+    /// Applies the active effects, and returns the winner if that ends the battle.
+    fn start_turn(&mut self) -> Option<Side> {
+        let mut winner = None;
+        for i in 0..self.effects.len() {
+            let index = self.effects[i].spell;
+            let effect = self.effect(index);
+            self.damage_boss(effect.damage);
+            self.player.mana += effect.mana;
+            self.effects[i].timer -= 1;
+            let timer = self.effects[i].timer;
+            let spell = &self.spellbook.spells()[index];
+            self.record(|| Event::Effect {
+                spell: spell.clone(),
+                timer,
+            });
+            if winner.is_none() {
+                winner = self.winner();
+            }
+            if timer == 0 {
+                self.record(|| Event::WoreOff(spell.clone()));
+            }
+        }
+        self.effects.retain(|effect| effect.timer > 0);
+        self.turn += 1;
+        winner
+    }
+
+    fn boss_attack(&mut self) {
+        let damage = self.boss.damage;
+        let armor = self.player_armor();
+        self.record(|| Event::Attack { damage, armor });
+        self.damage_player(damage)
+    }
+
+    fn key(&self) -> StateKey {
+        StateKey {
+            player: self.player.clone(),
+            boss_hp: self.boss.hp,
+            effects: self.effects.clone(),
+        }
+    }
+
+    /// Returns a lower bound on the mana still needed to win: the hit points of the boss that
+    /// the active effects won't take care of, at the cheapest rate of damage.
+    fn estimate(&self, rate: Option<DamageRate>) -> u32 {
+        let armor = self.config.boss_armor;
+        let pending = self
+            .effects
+            .iter()
+            .map(|effect| damage_through(self.effect(effect.spell).damage, armor) * effect.timer)
+            .sum::<u32>();
+        let remaining = u64::from(self.boss.hp.saturating_sub(pending));
+        match rate {
+            Some(rate) => {
+                let damage = u64::from(rate.damage);
+                let cost = (remaining * u64::from(rate.cost) + damage - 1) / damage;
+                cost.min(u64::from(u32::MAX)) as u32
+            }
+            None => 0,
+        }
+    }
+
+    fn player_wins(&self) -> bool {
+        self.boss.hp == 0
+    }
+
+    fn player_loses(&self) -> bool {
+        self.player.hp == 0
+    }
+}
+
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap instead of a max-heap.
+impl Ord for Game<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that the we flip the ordering on the bounds and costs.
+        other
+            .bound
+            .cmp(&self.bound)
+            .then_with(|| other.cost.cmp(&self.cost))
+            // Sort by lowest boss's HP.
+            .then_with(|| other.boss.hp.cmp(&self.boss.hp))
+            // Use other fields to distinguish.
+            .then_with(|| self.player.cmp(&other.player))
+            .then_with(|| self.boss.cmp(&other.boss))
+            .then_with(|| self.turn.cmp(&other.turn))
+            .then_with(|| self.effects.cmp(&other.effects))
+            .then_with(|| self.spells.cmp(&other.spells))
+    }
+}
+
+impl PartialOrd for Game<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Finds the cheapest game that the player wins, with an A* search on the mana spent.
+fn search<'a>(game: Game<'a>, stats: &mut SearchStats) -> Option<Game<'a>> {
+    let rate = DamageRate::best(game.spellbook, game.config.boss_armor);
+    let mut seen = HashSet::new();
+    let mut queue = BinaryHeap::<Game>::new();
+    queue.push(game);
+    while let Some(mut game) = queue.pop() {
+        if !game.spells.is_empty() {
+            // The heuristic only depends on the key, so the first of the equivalent games to
+            // come off the queue is also the cheapest.
+            if !seen.insert(game.key()) {
+                stats.duplicates += 1;
+                continue;
+            }
+            match game.boss_turn() {
+                Some(Side::Player) => return Some(game),
+                Some(Side::Boss) => continue,
+                None => {}
+            }
+        }
+        stats.expanded += 1;
+        match game.player_turn() {
+            Some(Side::Player) => return Some(game),
+            Some(Side::Boss) => continue,
+            None => {}
+        }
+        for spell in 0..game.spellbook.spells().len() {
+            if !game.can_cast(spell) {
+                continue;
+            }
+            let mut game = game.clone();
+            game.cast(spell);
+            game.bound = game.cost.saturating_add(game.estimate(rate));
+            // We don't yet evaluate the boss's turn here. Even if we can win (by poison damage),
+            // there may still be a different game state with a lower cost or a cheaper attack
+            // that can also defeat the boss.
+            // Instead, we push the game back onto the queue and evaluate the boss's turn when
+            // it pops back off the queue. Only at that point can we be *sure* that we've found
+            // the winning game with the lowest possible cost.
+            queue.push(game);
+        }
+        stats.max_queue = stats.max_queue.max(queue.len());
+    }
+    None
+}
+
+/// Finds the cheapest game that the player wins.
+fn cheapest<'a>(
+    spellbook: &'a Spellbook,
+    config: &'a BattleConfig,
+    boss: &Boss,
+    stats: &mut SearchStats,
+) -> Option<Game<'a>> {
+    let mut game = Game::new(spellbook, config, boss.clone());
+    match game.opening_turn() {
+        Some(Side::Player) => Some(game),
+        Some(Side::Boss) => None,
+        None => search(game, stats),
+    }
+}
+
+/// Plays a battle where the player casts the spells with the given indices in order.
+fn play(
+    spellbook: &Spellbook,
+    config: &BattleConfig,
+    boss: &Boss,
+    spells: &[usize],
+) -> Option<Battle> {
+    let mut game = Game::new(spellbook, config, boss.clone()).with_log();
+    let mut spells = spells.iter();
+    let winner = match game.opening_turn() {
+        Some(winner) => Some(winner),
+        None => loop {
+            if let Some(winner) = game.player_turn() {
+                break Some(winner);
+            }
+            let spell = match spells.next() {
+                Some(&spell) => spell,
+                None => break None,
+            };
+            if !game.can_cast(spell) {
+                return None;
+            }
+            game.cast(spell);
+            if let Some(winner) = game.boss_turn() {
+                break Some(winner);
+            }
+        },
+    };
+    Some(game.into_battle(winner))
+}
+
+/// Plays a battle where the player casts the named spells in order, and logs every turn.
+///
+/// The battle stops early when it runs out of spells, and returns `None` if one of the spells
+/// isn't in the spellbook or can't be cast when its turn comes.
+pub fn replay(
+    spellbook: &Spellbook,
+    config: &BattleConfig,
+    boss: &Boss,
+    spells: &[&str],
+) -> Option<Battle> {
+    let spells = spells
+        .iter()
+        .map(|name| spellbook.position(name))
+        .collect::<Option<Vec<_>>>()?;
+    play(spellbook, config, boss, &spells)
+}
+
+/// Finds the cheapest battle that the player wins, with its spells and the log of its turns,
+/// and how much work it took to find it.
+pub fn solve(
+    spellbook: &Spellbook,
+    config: &BattleConfig,
+    boss: &Boss,
+) -> (Option<Battle>, SearchStats) {
+    let mut stats = SearchStats::default();
+    let battle = cheapest(spellbook, config, boss, &mut stats)
+        .and_then(|game| play(spellbook, config, boss, &game.spells));
+    (battle, stats)
+}
+
+/// Finds the cost of the cheapest win for every value of the parameter, or `None` for the
+/// values where the player can't win.
+pub fn sweep(
+    spellbook: &Spellbook,
+    config: &BattleConfig,
+    boss: &Boss,
+    parameter: Parameter,
+    values: impl IntoIterator<Item = u32>,
+) -> Vec<(u32, Option<u32>)> {
+    values
+        .into_iter()
+        .map(|value| {
+            let config = parameter.apply(config, value);
+            let mut stats = SearchStats::default();
+            let cost = cheapest(spellbook, &config, boss, &mut stats).map(|game| game.cost);
+            (value, cost)
+        })
+        .collect()
+}
+
+#[aoc(day22, part1)]
+pub fn part1(boss: &Boss) -> u32 {
+    let config = BattleConfig::default();
+    let (battle, _) = solve(&Spellbook::default(), &config, boss);
+    let battle = battle.expect("no solution found");
+    battle.cost
+}
+
+#[aoc(day22, part2)]
+pub fn part2(boss: &Boss) -> u32 {
+    let config = BattleConfig::hard();
+    let (battle, _) = solve(&Spellbook::default(), &config, boss);
+    let battle = battle.expect("no solution found");
+    battle.cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "-- Player turn --
+- Player has 10 hit points, 0 armor, 250 mana
+- Boss has 13 hit points
+Player casts Poison.
+
+-- Boss turn --
+- Player has 10 hit points, 0 armor, 77 mana
+- Boss has 13 hit points
+Poison deals 3 damage; its timer is now 5.
+Boss attacks for 8 damage.
+
+-- Player turn --
+- Player has 2 hit points, 0 armor, 77 mana
+- Boss has 10 hit points
+Poison deals 3 damage; its timer is now 4.
+Player casts Magic Missile, dealing 4 damage.
+
+-- Boss turn --
+- Player has 2 hit points, 0 armor, 24 mana
+- Boss has 3 hit points
+Poison deals 3 damage. This kills the boss, and the player wins.
+";
+
+    #[test]
+    fn test_replay() {
+        let spellbook = Spellbook::default();
+        let config = BattleConfig {
+            player: Player { hp: 10, mana: 250 },
+            ..BattleConfig::default()
+        };
+        let boss = Boss { hp: 13, damage: 8 };
+        let spells = ["Poison", "Magic Missile"];
+        let battle = replay(&spellbook, &config, &boss, &spells).unwrap();
+        assert_eq!(battle.winner, Some(Side::Player));
+        assert_eq!(battle.cost, 226);
+        assert_eq!(battle.to_string(), EXAMPLE);
+
+        let boss = Boss { hp: 14, damage: 8 };
+        let spells = ["Recharge", "Shield", "Drain", "Poison", "Magic Missile"];
+        let battle = replay(&spellbook, &config, &boss, &spells).unwrap();
+        assert_eq!(battle.winner, Some(Side::Player));
+        assert_eq!(battle.turns.len(), 10);
+        assert_eq!(
+            battle.turns[7].to_string(),
+            "-- Boss turn --
+- Player has 2 hit points, 7 armor, 167 mana
+- Boss has 12 hit points
+Shield's timer is now 1.
+Poison deals 3 damage; its timer is now 5.
+Boss attacks for 8 - 7 = 1 damage.
+"
+        );
+        assert_eq!(
+            battle.turns[8].to_string(),
+            "-- Player turn --
+- Player has 1 hit point, 7 armor, 167 mana
+- Boss has 9 hit points
+Shield's timer is now 0.
+Shield wears off, decreasing armor by 7.
+Poison deals 3 damage; its timer is now 4.
+Player casts Magic Missile, dealing 4 damage.
+"
+        );
+
+        let battle = replay(&spellbook, &config, &boss, &spells[..2]).unwrap();
+        assert_eq!(battle.winner, None);
+        assert_eq!(battle.turns.len(), 5);
+        assert_eq!(
+            battle.turns[3].events.last(),
+            Some(&Event::Attack {
+                damage: 8,
+                armor: 7
+            })
+        );
+        let hard = BattleConfig {
+            drain: 1,
+            ..config.clone()
+        };
+        let battle = replay(&spellbook, &hard, &boss, &spells).unwrap();
+        assert_eq!(battle.winner, Some(Side::Boss));
+        assert!(battle
+            .to_string()
+            .ends_with("Player loses 1 hit point. This kills the player, and the boss wins.\n"));
+        assert_eq!(
+            replay(&spellbook, &config, &boss, &["Shield", "Shield"]),
+            None
+        );
+        assert_eq!(replay(&spellbook, &config, &boss, &["Fireball"]), None);
+    }
+
+    #[test]
+    fn test_config() {
+        let spellbook = Spellbook::default();
+        let boss = Boss { hp: 13, damage: 8 };
+        let armored = BattleConfig {
+            player: Player { hp: 10, mana: 250 },
+            boss_armor: 2,
+            ..BattleConfig::default()
+        };
+        let battle = replay(&spellbook, &armored, &boss, &["Poison", "Magic Missile"]).unwrap();
+        assert_eq!(battle.winner, Some(Side::Boss));
+        assert_eq!(
+            battle.turns[2].to_string(),
+            "-- Player turn --
+- Player has 2 hit points, 0 armor, 77 mana
+- Boss has 12 hit points, 2 armor
+Poison deals 3 - 2 = 1 damage; its timer is now 4.
+Player casts Magic Missile, dealing 4 - 2 = 2 damage.
+"
+        );
+
+        let boss_first = BattleConfig {
+            player: Player { hp: 20, mana: 250 },
+            first: Side::Boss,
+            ..BattleConfig::default()
+        };
+        let battle = solve(&spellbook, &boss_first, &boss).0.unwrap();
+        assert_eq!(battle.spells, vec!["Poison", "Magic Missile"]);
+        assert_eq!(battle.turns[0].side, Side::Boss);
+        assert_eq!(battle.turns[1].player_hp, 12);
+
+        let drained = BattleConfig {
+            drain: 3,
+            ..boss_first
+        };
+        let battle = replay(&spellbook, &drained, &boss, &[]).unwrap();
+        assert_eq!(battle.winner, None);
+        assert_eq!(
+            battle.turns[1].to_string(),
+            "-- Player turn --
+- Player has 12 hit points, 0 armor, 250 mana
+- Boss has 13 hit points
+Player loses 3 hit points.
+"
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let spellbook = Spellbook::default();
+        let config = BattleConfig {
+            player: Player { hp: 10, mana: 250 },
+            ..BattleConfig::default()
+        };
+        let boss = Boss { hp: 13, damage: 8 };
+        let battle = solve(&spellbook, &config, &boss).0.unwrap();
+        assert_eq!(battle.spells, vec!["Poison", "Magic Missile"]);
+        assert_eq!(battle.to_string(), EXAMPLE);
+        let boss = Boss { hp: 14, damage: 8 };
+        let battle = solve(&spellbook, &config, &boss).0.unwrap();
+        assert_eq!(battle.cost, 641);
+        assert_eq!(battle.winner, Some(Side::Player));
+    }
+
+    #[test]
+    fn test_search() {
+        let spellbook = Spellbook::default();
+        let config = BattleConfig::default();
+        let boss = Boss { hp: 13, damage: 8 };
+        // Poison deals 18 damage for 173 mana, the best rate of all spells.
+        let rate = DamageRate::best(&spellbook, 0);
+        assert_eq!(
+            rate,
+            Some(DamageRate {
+                cost: 173,
+                damage: 18
+            })
+        );
+        let mut game = Game::new(&spellbook, &config, boss.clone());
+        assert_eq!(game.estimate(rate), 125);
+        game.cast(spellbook.position("Poison").unwrap());
+        assert_eq!(game.estimate(rate), 0);
+        // With armor, Magic Missile and Drain do so little damage that Poison stays the best.
+        assert_eq!(DamageRate::best(&spellbook, 3).unwrap().cost, 173);
+
+        let config = BattleConfig {
+            player: Player { hp: 10, mana: 250 },
+            ..BattleConfig::default()
+        };
+        let boss = Boss { hp: 14, damage: 8 };
+        let (battle, stats) = solve(&spellbook, &config, &boss);
+        assert_eq!(battle.unwrap().cost, 641);
+        assert!(stats.expanded > 0 && stats.duplicates > 0);
+        assert!(stats.max_queue >= 5);
+    }
+
+    #[test]
+    fn test_sweep() {
+        let spellbook = Spellbook::default();
+        let config = BattleConfig {
+            player: Player { hp: 10, mana: 250 },
+            ..BattleConfig::default()
+        };
+        let boss = Boss { hp: 13, damage: 8 };
+        assert_eq!(
+            sweep(
+                &spellbook,
+                &config,
+                &boss,
+                Parameter::PlayerMana,
+                vec![100, 225, 226, 500]
+            ),
+            vec![(100, None), (225, None), (226, Some(226)), (500, Some(226))]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("Hit Points: 51\nDmg: 9"),
+            "2:1: expected `Damage:`, found `Dmg:`"
+        );
+    }
+}
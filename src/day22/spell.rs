@@ -0,0 +1,301 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::util::{parse_number, split_once, strip_prefix, strip_suffix, ParseError};
+
+/// An effect that lasts for `duration` turns, and applies at the start of every turn.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Effect {
+    pub duration: u32,
+    /// Damage dealt to the boss every turn.
+    pub damage: u32,
+    /// Armor given to the player while the effect is active.
+    pub armor: u32,
+    /// Mana provided to the player every turn.
+    pub mana: u32,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Spell {
+    pub name: String,
+    pub cost: u32,
+    /// Damage dealt to the boss right away.
+    pub damage: u32,
+    /// Hit points healed right away.
+    pub heal: u32,
+    pub effect: Option<Effect>,
+}
+
+/// Displays the spell in the format of a spellbook line.
+impl Display for Spell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} mana", self.name, self.cost)?;
+        if self.damage > 0 {
+            write!(f, ", {} damage", self.damage)?;
+        }
+        if self.heal > 0 {
+            write!(f, ", {} heal", self.heal)?;
+        }
+        if let Some(effect) = &self.effect {
+            let mut parts = Vec::new();
+            if effect.damage > 0 {
+                parts.push(format!("{} damage", effect.damage));
+            }
+            if effect.armor > 0 {
+                parts.push(format!("{} armor", effect.armor));
+            }
+            if effect.mana > 0 {
+                parts.push(format!("{} mana", effect.mana));
+            }
+            write!(f, ", {} turns", effect.duration)?;
+            if !parts.is_empty() {
+                write!(f, " of {}", parts.join(" and "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a line like `Drain: 73 mana, 2 damage, 2 heal` or `Shield: 113 mana, 6 turns of 7 armor`.
+///
+/// An effect that does nothing, but still lasts, is written without its parts, like `6 turns`.
+fn parse_spell(s: &str) -> Result<Spell, ParseError> {
+    let (name, properties) = split_once(s, s, ": ")?;
+    if name.trim().is_empty() {
+        return Err(ParseError::expected(s, name, "spell name"));
+    }
+    let mut properties = properties.split(", ");
+    let cost = strip_suffix(s, properties.next().unwrap(), " mana")?;
+    let mut spell = Spell {
+        name: name.to_string(),
+        cost: parse_number(s, cost)?,
+        damage: 0,
+        heal: 0,
+        effect: None,
+    };
+    for property in properties {
+        if let Some((duration, parts)) = property.split_once(" turns") {
+            if spell.effect.is_some() {
+                let reason = "a spell has at most one effect";
+                return Err(ParseError::invalid(s, property, "effect", reason));
+            }
+            let mut effect = Effect {
+                duration: parse_number(s, duration)?,
+                damage: 0,
+                armor: 0,
+                mana: 0,
+            };
+            let parts = if parts.is_empty() {
+                None
+            } else {
+                Some(strip_prefix(s, parts, " of ")?)
+            };
+            for part in parts.into_iter().flat_map(|parts| parts.split(" and ")) {
+                let (amount, kind) = split_once(s, part, " ")?;
+                let amount = parse_number(s, amount)?;
+                match kind {
+                    "damage" => effect.damage = amount,
+                    "armor" => effect.armor = amount,
+                    "mana" => effect.mana = amount,
+                    _ => return Err(ParseError::unknown(s, kind, "effect property")),
+                }
+            }
+            spell.effect = Some(effect);
+        } else {
+            let (amount, kind) = split_once(s, property, " ")?;
+            let amount = parse_number(s, amount)?;
+            match kind {
+                "damage" => spell.damage = amount,
+                "heal" => spell.heal = amount,
+                _ => return Err(ParseError::unknown(s, kind, "spell property")),
+            }
+        }
+    }
+    Ok(spell)
+}
+
+/// The spells that the player can choose from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Spellbook {
+    spells: Vec<Spell>,
+}
+
+impl Spellbook {
+    pub fn new(spells: Vec<Spell>) -> Self {
+        Self { spells }
+    }
+
+    pub fn spells(&self) -> &[Spell] {
+        &self.spells
+    }
+
+    /// Loads a spellbook from a file, in the format of [`FromStr`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        fs::read_to_string(path)?.parse().map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}", path.display(), err),
+            )
+        })
+    }
+
+    /// Returns the index of the spell with this name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.spells.iter().position(|spell| spell.name == name)
+    }
+}
+
+/// The spells from the puzzle.
+impl Default for Spellbook {
+    fn default() -> Self {
+        include_str!("spells.txt").parse().unwrap()
+    }
+}
+
+/// Parses a spellbook with a spell on every line, skipping blank lines and `#` comments.
+impl FromStr for Spellbook {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut spells = Vec::<Spell>::new();
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap().trim_end();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let spell = parse_spell(line).map_err(|err| err.offset(s, line))?;
+            if spells.iter().any(|other| other.name == spell.name) {
+                let name = &line[..spell.name.len()];
+                let err = ParseError::invalid(line, name, "spell", "already defined");
+                return Err(err.offset(s, line));
+            }
+            spells.push(spell);
+        }
+        Ok(Spellbook::new(spells))
+    }
+}
+
+impl Display for Spellbook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for spell in self.spells.iter() {
+            writeln!(f, "{}", spell)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let spellbook = Spellbook::default();
+        assert_eq!(spellbook.spells().len(), 5);
+        assert_eq!(
+            spellbook.spells()[1],
+            Spell {
+                name: "Drain".to_string(),
+                cost: 73,
+                damage: 2,
+                heal: 2,
+                effect: None,
+            }
+        );
+        assert_eq!(spellbook.position("Recharge"), Some(4));
+        assert_eq!(
+            spellbook.to_string().parse::<Spellbook>().unwrap(),
+            spellbook
+        );
+
+        let spellbook = "Blizzard: 300 mana, 2 damage, 3 turns of 1 damage and 2 armor"
+            .parse::<Spellbook>()
+            .unwrap();
+        assert_eq!(
+            spellbook.spells()[0].effect,
+            Some(Effect {
+                duration: 3,
+                damage: 1,
+                armor: 2,
+                mana: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let effect = |duration, damage, armor, mana| Effect {
+            duration,
+            damage,
+            armor,
+            mana,
+        };
+        let spell = |name: &str, effect| Spell {
+            name: name.to_string(),
+            cost: 10,
+            damage: 0,
+            heal: 0,
+            effect: Some(effect),
+        };
+        let spellbook = Spellbook::new(vec![
+            spell("Stall", effect(3, 0, 0, 0)),
+            spell("Storm", effect(2, 1, 2, 3)),
+        ]);
+        assert_eq!(
+            spellbook.to_string(),
+            "Stall: 10 mana, 3 turns\nStorm: 10 mana, 2 turns of 1 damage and 2 armor and 3 mana\n"
+        );
+        assert_eq!(
+            spellbook.to_string().parse::<Spellbook>().unwrap(),
+            spellbook
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = env::temp_dir().join("aoc-2015-spellbook");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("spells.txt");
+        fs::write(&path, Spellbook::default().to_string()).unwrap();
+        assert_eq!(Spellbook::load(&path).unwrap(), Spellbook::default());
+
+        fs::write(&path, "# Shields\nShield: 113 mana, 6 turns of 7 shield\n").unwrap();
+        let err = Spellbook::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            format!("{}:2:32: unknown effect property `shield`", path.display())
+        );
+        assert_eq!(
+            Spellbook::load(dir.join("missing.txt")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| s.parse::<Spellbook>().unwrap_err().to_string();
+        assert_eq!(
+            error("Drain: 73 mana, 2 damage, 2 heals"),
+            "1:29: unknown spell property `heals`"
+        );
+        assert_eq!(
+            error("# comment\nShield: 113 mana, 6 turns of 7 shield"),
+            "2:32: unknown effect property `shield`"
+        );
+        assert_eq!(
+            error("Drain: 73 mana\n\nDrain: 74 mana"),
+            "3:1: invalid spell `Drain`: already defined"
+        );
+        assert_eq!(
+            error("Drain: 73"),
+            "1:10: expected `mana`, found end of line"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use crate::util::{parse_number, split_once, ParseError};
+
+use super::Instruction;
+
+fn is_label(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Assembles a program written in the puzzle's instruction set, extended with labels,
+/// comments and blank lines.
+///
+/// A label is defined by a line starting with `name:`, optionally followed by an instruction,
+/// and can be used instead of the offset of a jump. Comments start with `;` and run until the
+/// end of the line. Plain puzzle input is valid, and so is the output of
+/// [`disassemble`](super::disassemble).
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, ParseError> {
+    // The instructions, with the address of every label.
+    let mut statements = Vec::new();
+    let mut labels = HashMap::<&str, (usize, usize)>::new();
+    for (line_number, line) in source.lines().enumerate() {
+        let mut statement = line.split(';').next().unwrap().trim();
+        while let Some((label, rest)) = statement.split_once(':') {
+            let label = label.trim_end();
+            if !is_label(label) {
+                return Err(ParseError::expected(source, label, "label"));
+            }
+            if let Some(&(_, defined)) = labels.get(label) {
+                let reason = format!("already defined on line {}", defined);
+                return Err(ParseError::invalid(source, label, "label", reason));
+            }
+            labels.insert(label, (statements.len(), line_number + 1));
+            statement = rest.trim_start();
+        }
+        if !statement.is_empty() {
+            statements.push(statement);
+        }
+    }
+
+    statements
+        .iter()
+        .enumerate()
+        .map(|(pc, &statement)| {
+            let offset = |target: &str| {
+                if is_label(target) {
+                    match labels.get(target) {
+                        Some(&(address, _)) => Ok(address as isize - pc as isize),
+                        None => Err(ParseError::unknown(source, target, "label")),
+                    }
+                } else {
+                    parse_number(source, target)
+                }
+            };
+            let register = |register: &str| {
+                register
+                    .parse()
+                    .map_err(|err: ParseError| err.offset(source, register))
+            };
+            let (mnemonic, operands) = split_once(source, statement, " ")?;
+            Ok(match mnemonic {
+                "jmp" => Instruction::Jump(offset(operands)?),
+                "jie" => {
+                    let (r, target) = split_once(source, operands, ", ")?;
+                    Instruction::JumpIfEven(register(r)?, offset(target)?)
+                }
+                "jio" => {
+                    let (r, target) = split_once(source, operands, ", ")?;
+                    Instruction::JumpIfOne(register(r)?, offset(target)?)
+                }
+                _ => statement
+                    .parse()
+                    .map_err(|err: ParseError| err.offset(source, statement))?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lazy_static::*;
+
+    use super::super::{disassemble, input_generator};
+    use super::*;
+
+    lazy_static! {
+        static ref TEST_INPUT: &'static str = r"
+; Counts the steps of the Collatz sequence starting at a, in b.
+    jio a, done
+loop:
+    inc b
+    jie a, even   ; a = 3a + 1
+    tpl a
+    inc a
+    jmp next
+even: hlf a
+next:
+
+    jio a, done
+    jmp loop
+done:"
+            .trim();
+    }
+
+    #[test]
+    fn test_assemble() {
+        let program = assemble(&TEST_INPUT).unwrap();
+        let expected = input_generator(
+            "jio a, +9
+inc b
+jie a, +4
+tpl a
+inc a
+jmp +2
+hlf a
+jio a, +2
+jmp -7",
+        )
+        .unwrap();
+        assert_eq!(program, expected);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let program = assemble(&TEST_INPUT).unwrap();
+        let text = program
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(input_generator(&text).unwrap(), program);
+        assert_eq!(assemble(&text).unwrap(), program);
+        assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            assemble("loop: inc a\n\njmp lop").unwrap_err().to_string(),
+            "3:5: unknown label `lop`"
+        );
+        assert_eq!(
+            assemble("loop: inc a\n; again\nloop:\njmp loop")
+                .unwrap_err()
+                .to_string(),
+            "3:1: invalid label `loop`: already defined on line 1"
+        );
+        assert_eq!(
+            assemble("inc a\n  jie A, +2").unwrap_err().to_string(),
+            "2:7: unknown register `A`"
+        );
+        assert_eq!(
+            assemble("inc a\n  hlf A").unwrap_err().to_string(),
+            "2:7: unknown register `A`"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::Instruction;
+
+/// Names every address that a jump in the program lands on: `L1`, `L2`, ... in order of
+/// their address, and `end` for the address right after the last instruction.
+pub fn labels(program: &[Instruction]) -> BTreeMap<usize, String> {
+    let mut targets = program
+        .iter()
+        .enumerate()
+        .filter_map(|(pc, instruction)| instruction.jump_target(pc))
+        .filter(|&target| target >= 0 && target as usize <= program.len())
+        .map(|target| (target as usize, String::new()))
+        .collect::<BTreeMap<_, _>>();
+    for (i, (&target, label)) in targets.iter_mut().enumerate() {
+        *label = if target == program.len() {
+            "end".to_string()
+        } else {
+            format!("L{}", i + 1)
+        };
+    }
+    targets
+}
+
+/// Renders the program with a label in front of every jump target, and jumps to those labels
+/// instead of relative offsets.
+///
+/// Every instruction is followed by a comment with its address, and for jumps the absolute
+/// address they jump to. Jumps out of the program keep their relative offset.
+pub fn disassemble(program: &[Instruction]) -> String {
+    let labels = labels(program);
+    let mut output = String::new();
+    for (pc, instruction) in program.iter().enumerate() {
+        if let Some(label) = labels.get(&pc) {
+            writeln!(output, "{}:", label).unwrap();
+        }
+        let target = instruction.jump_target(pc);
+        let label = target
+            .filter(|&target| target >= 0)
+            .and_then(|target| labels.get(&(target as usize)));
+        let text = match (instruction, label) {
+            (Instruction::Jump(_), Some(label)) => format!("jmp {}", label),
+            (Instruction::JumpIfEven(register, _), Some(label)) => {
+                format!("jie {}, {}", register, label)
+            }
+            (Instruction::JumpIfOne(register, _), Some(label)) => {
+                format!("jio {}, {}", register, label)
+            }
+            _ => instruction.to_string(),
+        };
+        let comment = match target {
+            Some(target) if label.is_some() => format!("{} -> {}", pc, target),
+            Some(target) => format!("{} -> {} (outside the program)", pc, target),
+            None => pc.to_string(),
+        };
+        writeln!(output, "    {:<14}; {}", text, comment).unwrap();
+    }
+    if let Some(label) = labels.get(&program.len()) {
+        writeln!(output, "{}:", label).unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::input_generator;
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        let program = input_generator(
+            "jio a, +3
+inc b
+jmp -1
+jie a, +2
+jmp -9",
+        )
+        .unwrap();
+        assert_eq!(
+            disassemble(&program),
+            "    jio a, L2     ; 0 -> 3
+L1:
+    inc b         ; 1
+    jmp L1        ; 2 -> 1
+L2:
+    jie a, end    ; 3 -> 5
+    jmp -9        ; 4 -> -5 (outside the program)
+end:
+"
+        );
+    }
+}
@@ -0,0 +1,462 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter, Write};
+
+use super::{Instruction, Register};
+
+/// When an edge between basic blocks is taken.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Condition {
+    Always,
+    Even(Register),
+    Odd(Register),
+    One(Register),
+    NotOne(Register),
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Always => Ok(()),
+            Condition::Even(register) => write!(f, "{} is even", register),
+            Condition::Odd(register) => write!(f, "{} is odd", register),
+            Condition::One(register) => write!(f, "{} == 1", register),
+            Condition::NotOne(register) => write!(f, "{} != 1", register),
+        }
+    }
+}
+
+/// Where an edge between basic blocks goes to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Target {
+    /// The basic block with this index.
+    Block(usize),
+    /// Past the end of the program, which ends it.
+    Exit,
+    /// This address before the start of the program.
+    Negative(isize),
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Block(block) => write!(f, "block {}", block),
+            Target::Exit => write!(f, "exit"),
+            Target::Negative(address) => write!(f, "address {} (outside the program)", address),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Edge {
+    pub condition: Condition,
+    pub target: Target,
+}
+
+/// A run of instructions that is only ever entered at its first instruction, and only
+/// ever left after its last instruction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BasicBlock {
+    /// The address of the first instruction.
+    pub start: usize,
+    /// The address right after the last instruction.
+    pub end: usize,
+    pub edges: Vec<Edge>,
+}
+
+/// A natural loop: the blocks that can reach one of the `latches` without passing through
+/// the `header`, where every latch jumps back to the header.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Loop {
+    pub header: usize,
+    pub latches: Vec<usize>,
+    pub blocks: BTreeSet<usize>,
+}
+
+/// The basic blocks of a program and the edges between them.
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    program: Vec<Instruction>,
+    blocks: Vec<BasicBlock>,
+    loops: Vec<Loop>,
+}
+
+impl ControlFlowGraph {
+    pub fn new(program: &[Instruction]) -> Self {
+        // Every jump target and every instruction after a jump starts a new block.
+        let mut leaders = BTreeSet::new();
+        if !program.is_empty() {
+            leaders.insert(0);
+        }
+        for (pc, instruction) in program.iter().enumerate() {
+            if let Some(target) = instruction.jump_target(pc) {
+                if target >= 0 && (target as usize) < program.len() {
+                    leaders.insert(target as usize);
+                }
+                if pc + 1 < program.len() {
+                    leaders.insert(pc + 1);
+                }
+            }
+        }
+        let leaders = leaders.into_iter().collect::<Vec<_>>();
+        let mut block_of = vec![0; program.len()];
+        for (block, &start) in leaders.iter().enumerate() {
+            let end = leaders.get(block + 1).copied().unwrap_or(program.len());
+            for block_of in &mut block_of[start..end] {
+                *block_of = block;
+            }
+        }
+        let target = |address: isize| {
+            if address < 0 {
+                Target::Negative(address)
+            } else if address as usize >= program.len() {
+                Target::Exit
+            } else {
+                Target::Block(block_of[address as usize])
+            }
+        };
+        let blocks = leaders
+            .iter()
+            .enumerate()
+            .map(|(block, &start)| {
+                let end = leaders.get(block + 1).copied().unwrap_or(program.len());
+                let pc = end - 1;
+                let next = target(end as isize);
+                let jump = program[pc].jump_target(pc).map(target);
+                let edge = |condition, target| Edge { condition, target };
+                let edges = match (program[pc], jump) {
+                    (Instruction::Jump(_), Some(jump)) => vec![edge(Condition::Always, jump)],
+                    (Instruction::JumpIfEven(register, _), Some(jump)) => vec![
+                        edge(Condition::Even(register), jump),
+                        edge(Condition::Odd(register), next),
+                    ],
+                    (Instruction::JumpIfOne(register, _), Some(jump)) => vec![
+                        edge(Condition::One(register), jump),
+                        edge(Condition::NotOne(register), next),
+                    ],
+                    _ => vec![edge(Condition::Always, next)],
+                };
+                BasicBlock { start, end, edges }
+            })
+            .collect::<Vec<_>>();
+        let loops = find_loops(&blocks);
+        Self {
+            program: program.to_vec(),
+            blocks,
+            loops,
+        }
+    }
+
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    /// Returns the loops, ordered by their header.
+    pub fn loops(&self) -> &[Loop] {
+        &self.loops
+    }
+
+    /// Returns the index of the block containing the instruction at `pc`.
+    pub fn block_at(&self, pc: usize) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|block| block.start <= pc && pc < block.end)
+    }
+
+    /// Renders the graph as a Graphviz DOT graph, with every loop header drawn with a double
+    /// border and the edges back to it dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph cfg {\n    node [shape=box, fontname=monospace];\n");
+        for (index, block) in self.blocks.iter().enumerate() {
+            let mut label = String::new();
+            for pc in block.start..block.end {
+                write!(label, "{}: {}\\l", pc, self.program[pc]).unwrap();
+            }
+            let header = self.loops.iter().any(|l| l.header == index);
+            let style = if header { ", peripheries=2" } else { "" };
+            writeln!(dot, "    b{} [label=\"{}\"{}];", index, label, style).unwrap();
+        }
+        let mut exits = BTreeSet::new();
+        for (index, block) in self.blocks.iter().enumerate() {
+            for edge in block.edges.iter() {
+                let node = match edge.target {
+                    Target::Block(target) => format!("b{}", target),
+                    Target::Exit => "exit".to_string(),
+                    Target::Negative(address) => {
+                        exits.insert(address);
+                        format!("\"{}\"", address)
+                    }
+                };
+                let mut attributes = Vec::new();
+                if edge.condition != Condition::Always {
+                    attributes.push(format!("label=\"{}\"", edge.condition));
+                }
+                let back = self
+                    .loops
+                    .iter()
+                    .any(|l| edge.target == Target::Block(l.header) && l.latches.contains(&index));
+                if back {
+                    attributes.push("style=dashed".to_string());
+                }
+                let attributes = if attributes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", attributes.join(", "))
+                };
+                writeln!(dot, "    b{} -> {}{};", index, node, attributes).unwrap();
+            }
+        }
+        dot.push_str("    exit [shape=doublecircle];\n");
+        for address in exits {
+            writeln!(dot, "    \"{}\" [shape=octagon];", address).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Display for ControlFlowGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, block) in self.blocks.iter().enumerate() {
+            writeln!(f, "block {}:", index)?;
+            for pc in block.start..block.end {
+                writeln!(f, "    {:>4}  {}", pc, self.program[pc])?;
+            }
+            for edge in block.edges.iter() {
+                match edge.condition {
+                    Condition::Always => writeln!(f, "    -> {}", edge.target)?,
+                    condition => writeln!(f, "    -> {} if {}", edge.target, condition)?,
+                }
+            }
+        }
+        for l in self.loops.iter() {
+            let blocks = l
+                .blocks
+                .iter()
+                .map(|block| block.to_string())
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "loop at block {}: blocks {}",
+                l.header,
+                blocks.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether every block can be reached from the first block without passing through
+/// `avoid`, if any.
+fn reachable_avoiding<I, S>(blocks: usize, successors: S, avoid: Option<usize>) -> Vec<bool>
+where
+    I: Iterator<Item = usize>,
+    S: Fn(usize) -> I,
+{
+    let mut reachable = vec![false; blocks];
+    let mut pending = Vec::new();
+    if blocks > 0 && avoid != Some(0) {
+        reachable[0] = true;
+        pending.push(0);
+    }
+    while let Some(block) = pending.pop() {
+        for successor in successors(block) {
+            if !reachable[successor] && avoid != Some(successor) {
+                reachable[successor] = true;
+                pending.push(successor);
+            }
+        }
+    }
+    reachable
+}
+
+/// Finds the natural loops, from the back edges: the edges to a block that dominates the block
+/// they leave, so that every path from the first block to the latch passes through the header.
+///
+/// Cycles that can be entered at more than one block (irreducible ones) have no such header,
+/// and are not loops.
+fn find_loops(blocks: &[BasicBlock]) -> Vec<Loop> {
+    let successors = |block: usize| {
+        blocks[block]
+            .edges
+            .iter()
+            .filter_map(|edge| match edge.target {
+                Target::Block(target) => Some(target),
+                _ => None,
+            })
+    };
+    let mut predecessors = vec![Vec::new(); blocks.len()];
+    for block in 0..blocks.len() {
+        for successor in successors(block) {
+            predecessors[successor].push(block);
+        }
+    }
+
+    let reachable = reachable_avoiding(blocks.len(), successors, None);
+    let mut latches = BTreeMap::<usize, Vec<usize>>::new();
+    for header in (0..blocks.len()).filter(|&header| reachable[header]) {
+        let mut sources = predecessors[header]
+            .iter()
+            .copied()
+            .filter(|&block| reachable[block])
+            .collect::<Vec<_>>();
+        sources.dedup();
+        if sources.is_empty() {
+            continue;
+        }
+        // The header dominates the blocks that can no longer be reached without it.
+        let avoiding = reachable_avoiding(blocks.len(), successors, Some(header));
+        for block in sources {
+            if block == header || !avoiding[block] {
+                latches.entry(header).or_default().push(block);
+            }
+        }
+    }
+
+    latches
+        .into_iter()
+        .map(|(header, latches)| {
+            let mut body = BTreeSet::new();
+            body.insert(header);
+            let mut pending = latches.clone();
+            while let Some(block) = pending.pop() {
+                if body.insert(block) {
+                    let reachable_predecessors = predecessors[block]
+                        .iter()
+                        .filter(|&&predecessor| reachable[predecessor]);
+                    pending.extend(reachable_predecessors);
+                }
+            }
+            Loop {
+                header,
+                latches,
+                blocks: body,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lazy_static::*;
+
+    use super::super::input_generator;
+    use super::*;
+
+    lazy_static! {
+        // The shape of the puzzle input: a Collatz loop, counting its steps in `b`.
+        static ref TEST_INPUT: &'static str = r"
+jio a, +2
+tpl a
+jio a, +8
+inc b
+jie a, +4
+tpl a
+inc a
+jmp +2
+hlf a
+jmp -7"
+            .trim();
+    }
+
+    #[test]
+    fn test_blocks() {
+        let program = input_generator(&TEST_INPUT).unwrap();
+        let graph = ControlFlowGraph::new(&program);
+        let ranges = graph
+            .blocks()
+            .iter()
+            .map(|block| (block.start, block.end))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![(0, 1), (1, 2), (2, 3), (3, 5), (5, 8), (8, 9), (9, 10)]
+        );
+        assert_eq!(
+            graph.blocks()[3].edges,
+            vec![
+                Edge {
+                    condition: Condition::Even(Register::A),
+                    target: Target::Block(5),
+                },
+                Edge {
+                    condition: Condition::Odd(Register::A),
+                    target: Target::Block(4),
+                },
+            ]
+        );
+        assert_eq!(graph.blocks()[2].edges[0].target, Target::Exit);
+        assert_eq!(graph.block_at(6), Some(4));
+        assert_eq!(
+            graph.loops(),
+            &[Loop {
+                header: 2,
+                latches: vec![6],
+                blocks: vec![2, 3, 4, 5, 6].into_iter().collect(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_irreducible() {
+        // Blocks 1 and 2 jump to each other, but both are entered from block 0, so neither
+        // dominates the other and they form no loop. The loop at block 3 is still found.
+        let program = input_generator(
+            "jie a, +3
+inc b
+jio a, +2
+jmp -2
+inc a
+jie a, -1",
+        )
+        .unwrap();
+        let graph = ControlFlowGraph::new(&program);
+        let ranges = graph
+            .blocks()
+            .iter()
+            .map(|block| (block.start, block.end))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![(0, 1), (1, 3), (3, 4), (4, 6)]);
+        assert_eq!(
+            graph.loops(),
+            &[Loop {
+                header: 3,
+                latches: vec![3],
+                blocks: vec![3].into_iter().collect(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let program = input_generator("inc a\njie a, -1\njmp -5").unwrap();
+        let graph = ControlFlowGraph::new(&program);
+        assert_eq!(
+            graph.to_string(),
+            "block 0:
+       0  inc a
+       1  jie a, -1
+    -> block 0 if a is even
+    -> block 1 if a is odd
+block 1:
+       2  jmp -5
+    -> address -3 (outside the program)
+loop at block 0: blocks 0
+"
+        );
+        assert_eq!(
+            graph.to_dot(),
+            r#"digraph cfg {
+    node [shape=box, fontname=monospace];
+    b0 [label="0: inc a\l1: jie a, -1\l", peripheries=2];
+    b1 [label="2: jmp -5\l"];
+    b0 -> b0 [label="a is even", style=dashed];
+    b0 -> b1 [label="a is odd"];
+    b1 -> "-3";
+    exit [shape=doublecircle];
+    "-3" [shape=octagon];
+}
+"#
+        );
+    }
+}
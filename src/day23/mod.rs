@@ -0,0 +1,191 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{parse_lines, parse_number, split_once, ParseError};
+
+pub use self::assemble::*;
+pub use self::disassemble::*;
+pub use self::flow::*;
+pub use self::value::*;
+pub use self::vm::*;
+
+mod assemble;
+mod disassemble;
+mod flow;
+mod value;
+mod vm;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    Half(Register),
+    Triple(Register),
+    Increment(Register),
+    Jump(isize),
+    JumpIfEven(Register, isize),
+    JumpIfOne(Register, isize),
+}
+
+impl Instruction {
+    /// Returns the offset that this instruction jumps by, if it is a jump.
+    pub fn jump_offset(&self) -> Option<isize> {
+        match *self {
+            Instruction::Jump(offset)
+            | Instruction::JumpIfEven(_, offset)
+            | Instruction::JumpIfOne(_, offset) => Some(offset),
+            _ => None,
+        }
+    }
+
+    /// Returns the address that this instruction at `pc` jumps to, if it is a jump.
+    pub fn jump_target(&self, pc: usize) -> Option<isize> {
+        self.jump_offset().map(|offset| pc as isize + offset)
+    }
+}
+
+/// A register, named `a` to `z` in the assembly language.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Register(pub usize);
+
+impl Register {
+    pub const A: Register = Register(0);
+    pub const B: Register = Register(1);
+}
+
+/// The number of registers that can be named in the assembly language.
+pub const MAX_REGISTERS: usize = 26;
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (instruction, s) = split_once(line, line, " ")?;
+        let register = |s: &str| s.parse().map_err(|err: ParseError| err.offset(line, s));
+        Ok(match instruction {
+            "hlf" => Instruction::Half(register(s)?),
+            "tpl" => Instruction::Triple(register(s)?),
+            "inc" => Instruction::Increment(register(s)?),
+            "jmp" => Instruction::Jump(parse_number(line, s)?),
+            "jie" => {
+                let (r, offset) = split_once(line, s, ", ")?;
+                Instruction::JumpIfEven(register(r)?, parse_number(line, offset)?)
+            }
+            "jio" => {
+                let (r, offset) = split_once(line, s, ", ")?;
+                Instruction::JumpIfOne(register(r)?, parse_number(line, offset)?)
+            }
+            _ => return Err(ParseError::unknown(line, instruction, "instruction")),
+        })
+    }
+}
+
+impl FromStr for Register {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            &[name @ b'a'..=b'z'] => Ok(Register(usize::from(name - b'a'))),
+            _ => Err(ParseError::unknown(s, s, "register")),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Half(register) => write!(f, "hlf {}", register),
+            Instruction::Triple(register) => write!(f, "tpl {}", register),
+            Instruction::Increment(register) => write!(f, "inc {}", register),
+            Instruction::Jump(offset) => write!(f, "jmp {:+}", offset),
+            Instruction::JumpIfEven(register, offset) => {
+                write!(f, "jie {}, {:+}", register, offset)
+            }
+            Instruction::JumpIfOne(register, offset) => write!(f, "jio {}, {:+}", register, offset),
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0 < MAX_REGISTERS {
+            write!(f, "{}", char::from(b'a' + self.0 as u8))
+        } else {
+            write!(f, "r{}", self.0)
+        }
+    }
+}
+
+#[aoc_generator(day23)]
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input, str::parse)
+}
+
+/// Runs the program with the given value in register `a`, and returns register `b`.
+fn run(program: &[Instruction], a: i32) -> Result<i32, Halt> {
+    let mut computer = Computer::new(program.to_vec(), 2)?
+        .with_loop_detection()
+        .with_fast_forward();
+    computer.set_register(Register::A, a);
+    match computer.run() {
+        Halt::RanOffEnd => Ok(*computer.register(Register::B)),
+        halt => Err(halt),
+    }
+}
+
+#[aoc(day23, part1)]
+pub fn part1(input: &[Instruction]) -> Result<i32, Halt> {
+    run(input, 0)
+}
+
+#[aoc(day23, part2)]
+pub fn part2(input: &[Instruction]) -> Result<i32, Halt> {
+    run(input, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use lazy_static::*;
+
+    use super::*;
+
+    lazy_static! {
+        static ref TEST_INPUT: &'static str = r"
+inc a
+jio a, +2
+tpl a
+inc a"
+            .trim();
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let mut computer = Computer::<i32>::new(input, 2).unwrap();
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(*computer.register(Register::A), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let lines = input
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.join("\n"), *TEST_INPUT);
+        assert_eq!(Instruction::Jump(-7).to_string(), "jmp -7");
+        assert_eq!(
+            "hlf A".parse::<Instruction>().unwrap_err().to_string(),
+            "1:5: unknown register `A`"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("inc a\nmul a"), "2:1: unknown instruction `mul`");
+        assert_eq!(
+            error("inc a\njmp +x"),
+            "2:5: invalid number `+x`: invalid digit found in string"
+        );
+    }
+}
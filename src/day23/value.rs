@@ -0,0 +1,107 @@
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use num_bigint::BigInt;
+
+/// A type that the registers of a [`Computer`](super::Computer) can hold.
+///
+/// The operations that can overflow return `None` when they do, which halts the computer
+/// with [`Halt::Overflow`](super::Halt::Overflow).
+pub trait Value: Clone + Eq + Hash + Debug + Display {
+    fn zero() -> Self;
+
+    /// Halves the value, rounding towards zero.
+    fn half(&self) -> Self;
+
+    fn checked_triple(&self) -> Option<Self>;
+
+    fn checked_increment(&self) -> Option<Self>;
+
+    fn is_even(&self) -> bool;
+
+    fn is_one(&self) -> bool;
+
+    /// Returns the value as an `i128`, if it fits.
+    fn to_i128(&self) -> Option<i128>;
+
+    /// Adds `n` to the value, if the result fits.
+    fn checked_add_i128(&self, n: i128) -> Option<Self>;
+}
+
+macro_rules! impl_value {
+    ($($t:ty),*) => {
+        $(
+            impl Value for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn half(&self) -> Self {
+                    self / 2
+                }
+
+                fn checked_triple(&self) -> Option<Self> {
+                    self.checked_mul(3)
+                }
+
+                fn checked_increment(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn is_even(&self) -> bool {
+                    self % 2 == 0
+                }
+
+                fn is_one(&self) -> bool {
+                    *self == 1
+                }
+
+                fn to_i128(&self) -> Option<i128> {
+                    i128::try_from(*self).ok()
+                }
+
+                fn checked_add_i128(&self, n: i128) -> Option<Self> {
+                    let sum = self.to_i128()?.checked_add(n)?;
+                    Self::try_from(sum).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_value!(i32, i64, i128, u32, u64, u128);
+
+impl Value for BigInt {
+    fn zero() -> Self {
+        BigInt::from(0)
+    }
+
+    fn half(&self) -> Self {
+        self / 2
+    }
+
+    fn checked_triple(&self) -> Option<Self> {
+        Some(self * 3)
+    }
+
+    fn checked_increment(&self) -> Option<Self> {
+        Some(self + 1)
+    }
+
+    fn is_even(&self) -> bool {
+        !self.bit(0)
+    }
+
+    fn is_one(&self) -> bool {
+        *self == BigInt::from(1)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        i128::try_from(self).ok()
+    }
+
+    fn checked_add_i128(&self, n: i128) -> Option<Self> {
+        Some(self + n)
+    }
+}
@@ -0,0 +1,575 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use super::{Instruction, Register, Value, MAX_REGISTERS};
+
+/// Why a [`Computer`] stopped running.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Halt {
+    /// The program counter moved past the last instruction. This is how programs end normally.
+    RanOffEnd,
+    /// The jump at `pc` would move the program counter before the first instruction.
+    JumpedNegative { pc: usize, offset: isize },
+    /// The computer executed as many instructions as it was allowed to.
+    StepLimit { steps: usize },
+    /// The instruction at `pc` uses a register that the computer doesn't have.
+    InvalidRegister { pc: usize, register: Register },
+    /// The program never halts: it keeps looping back to `pc`.
+    InfiniteLoop { pc: usize },
+    /// The instruction at `pc` would overflow the value in `register`.
+    Overflow { pc: usize, register: Register },
+    /// The computer was asked for more than [`MAX_REGISTERS`] registers, so it never started.
+    TooManyRegisters { registers: usize },
+}
+
+impl Display for Halt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Halt::RanOffEnd => write!(f, "ran off the end of the program"),
+            Halt::JumpedNegative { pc, offset } => write!(
+                f,
+                "jump at {} by {:+} is before the start of the program",
+                pc, offset
+            ),
+            Halt::StepLimit { steps } => write!(f, "step limit reached after {} steps", steps),
+            Halt::InvalidRegister { pc, register } => {
+                write!(
+                    f,
+                    "instruction at {} uses unknown register {}",
+                    pc, register
+                )
+            }
+            Halt::InfiniteLoop { pc } => write!(f, "infinite loop at {}", pc),
+            Halt::Overflow { pc, register } => {
+                write!(f, "instruction at {} overflows register {}", pc, register)
+            }
+            Halt::TooManyRegisters { registers } => write!(
+                f,
+                "{} registers requested, but at most {} are supported",
+                registers, MAX_REGISTERS
+            ),
+        }
+    }
+}
+
+impl Error for Halt {}
+
+/// The state of a [`Computer`] right before it executed an instruction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceEntry<V = i32> {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub registers: Vec<V>,
+}
+
+impl<V: Display> Display for TraceEntry<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let registers = self
+            .registers
+            .iter()
+            .enumerate()
+            .map(|(i, value)| format!("{}={}", Register(i), value))
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "{:>4}  {:<10} {}",
+            self.pc,
+            self.instruction.to_string(),
+            registers.join(" ")
+        )
+    }
+}
+
+/// A loop that only increments registers, until a `jio` finds that one of them became 1.
+///
+/// The number of iterations such a loop runs for can be computed up front.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct CountingLoop {
+    /// The register tested by the `jio` that exits the loop.
+    register: Register,
+    /// How much the tested register is incremented by before the test, in each iteration.
+    before_test: i64,
+    /// How much each register is incremented by in each iteration.
+    increments: Vec<i64>,
+    /// The number of instructions executed in each iteration.
+    steps: usize,
+}
+
+/// Recognizes a counting loop, starting at `header` and ending with a jump back to it.
+fn counting_loop(program: &[Instruction], header: usize, registers: usize) -> Option<CountingLoop> {
+    let mut increments = vec![0; registers];
+    let mut test = None;
+    let mut pc = header;
+    for steps in 0..program.len() {
+        match *program.get(pc)? {
+            Instruction::Increment(register) => {
+                *increments.get_mut(register.0)? += 1;
+                pc += 1;
+            }
+            Instruction::JumpIfOne(register, _) if test.is_none() && register.0 < registers => {
+                test = Some((register, increments[register.0]));
+                pc += 1;
+            }
+            Instruction::Jump(offset) => {
+                let target = pc as isize + offset;
+                if target < 0 {
+                    return None;
+                }
+                pc = target as usize;
+            }
+            _ => return None,
+        }
+        if pc == header {
+            let (register, before_test) = test?;
+            return Some(CountingLoop {
+                register,
+                before_test,
+                increments,
+                steps: steps + 1,
+            });
+        }
+    }
+    None
+}
+
+/// A computer running a program in the day 23 instruction set, with registers of type `V`.
+#[derive(Debug, Clone)]
+pub struct Computer<V = i32> {
+    program: Vec<Instruction>,
+    pc: usize,
+    registers: Vec<V>,
+    steps: usize,
+    step_limit: Option<usize>,
+    trace: Option<Vec<TraceEntry<V>>>,
+    /// The states right after every backward jump, if loops are detected.
+    seen: Option<HashSet<(usize, Vec<V>)>>,
+    /// The counting loops by their header, if those are fast-forwarded.
+    counting_loops: Option<HashMap<usize, CountingLoop>>,
+}
+
+impl<V: Value> Computer<V> {
+    /// Creates a computer with the given number of registers, which all start at zero.
+    ///
+    /// Fails with [`Halt::TooManyRegisters`] for more than [`MAX_REGISTERS`] registers.
+    pub fn new(program: Vec<Instruction>, registers: usize) -> Result<Self, Halt> {
+        if registers > MAX_REGISTERS {
+            return Err(Halt::TooManyRegisters { registers });
+        }
+        Ok(Self {
+            program,
+            pc: 0,
+            registers: vec![V::zero(); registers],
+            steps: 0,
+            step_limit: None,
+            trace: None,
+            seen: None,
+            counting_loops: None,
+        })
+    }
+
+    /// Stops the computer after it executed `steps` instructions in total.
+    pub fn with_step_limit(mut self, steps: usize) -> Self {
+        self.step_limit = Some(steps);
+        self
+    }
+
+    /// Records every executed instruction, see [`Computer::trace`].
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// Halts with [`Halt::InfiniteLoop`] as soon as the computer gets into the same state twice.
+    ///
+    /// Every loop contains a backward jump, so only the states right after those are remembered.
+    pub fn with_loop_detection(mut self) -> Self {
+        self.seen = Some(HashSet::new());
+        self
+    }
+
+    /// Skips over all but the last iteration of simple counting loops: loops that only
+    /// increment registers, until a `jio` finds that one of them became 1. If that never
+    /// happens, halts with [`Halt::InfiniteLoop`] instead.
+    ///
+    /// The skipped iterations do count as steps, but aren't traced.
+    pub fn with_fast_forward(mut self) -> Self {
+        let counting_loops = self
+            .program
+            .iter()
+            .enumerate()
+            .filter_map(|(pc, instruction)| instruction.jump_target(pc))
+            .filter_map(|target| {
+                let header = usize::try_from(target).ok()?;
+                let counting_loop = counting_loop(&self.program, header, self.registers.len())?;
+                Some((header, counting_loop))
+            })
+            .collect();
+        self.counting_loops = Some(counting_loops);
+        self
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the number of instructions executed so far, saturating at `usize::MAX`.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn register(&self, register: Register) -> &V {
+        &self.registers[register.0]
+    }
+
+    pub fn set_register(&mut self, register: Register, value: V) {
+        self.registers[register.0] = value;
+    }
+
+    pub fn registers(&self) -> &[V] {
+        &self.registers
+    }
+
+    /// Returns the executed instructions, oldest first, or nothing if tracing isn't enabled.
+    pub fn trace(&self) -> &[TraceEntry<V>] {
+        self.trace.as_ref().map_or(&[], |trace| trace.as_slice())
+    }
+
+    /// Runs until the computer halts.
+    pub fn run(&mut self) -> Halt {
+        loop {
+            if let Some(halt) = self.step() {
+                return halt;
+            }
+        }
+    }
+
+    /// Executes a single instruction, or returns why the computer can't.
+    pub fn step(&mut self) -> Option<Halt> {
+        if let Some(steps) = self.step_limit {
+            if self.steps >= steps {
+                return Some(Halt::StepLimit { steps });
+            }
+        }
+        let instruction = match self.program.get(self.pc) {
+            Some(&instruction) => instruction,
+            None => return Some(Halt::RanOffEnd),
+        };
+        if let Some(halt) = self.fast_forward() {
+            return Some(halt);
+        }
+        let register = match instruction {
+            Instruction::Half(register)
+            | Instruction::Triple(register)
+            | Instruction::Increment(register)
+            | Instruction::JumpIfEven(register, _)
+            | Instruction::JumpIfOne(register, _) => Some(register),
+            Instruction::Jump(_) => None,
+        };
+        if let Some(register) = register {
+            if register.0 >= self.registers.len() {
+                return Some(Halt::InvalidRegister {
+                    pc: self.pc,
+                    register,
+                });
+            }
+        }
+        let pc = self.pc;
+        let overflow = |register| Some(Halt::Overflow { pc, register });
+        // The register to write to and its new value, and how far to jump.
+        let (write, offset) = match instruction {
+            Instruction::Half(register) => (Some((register, self.registers[register.0].half())), 1),
+            Instruction::Triple(register) => match self.registers[register.0].checked_triple() {
+                Some(value) => (Some((register, value)), 1),
+                None => return overflow(register),
+            },
+            Instruction::Increment(register) => {
+                match self.registers[register.0].checked_increment() {
+                    Some(value) => (Some((register, value)), 1),
+                    None => return overflow(register),
+                }
+            }
+            Instruction::Jump(offset) => (None, offset),
+            Instruction::JumpIfEven(register, offset) => {
+                let taken = self.registers[register.0].is_even();
+                (None, if taken { offset } else { 1 })
+            }
+            Instruction::JumpIfOne(register, offset) => {
+                let taken = self.registers[register.0].is_one();
+                (None, if taken { offset } else { 1 })
+            }
+        };
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(TraceEntry {
+                pc: self.pc,
+                instruction,
+                registers: self.registers.clone(),
+            });
+        }
+        self.steps = self.steps.saturating_add(1);
+        if let Some((register, value)) = write {
+            self.registers[register.0] = value;
+        }
+        if let Some(halt) = self.jump(offset) {
+            return Some(halt);
+        }
+        if offset <= 0 {
+            if let Some(seen) = self.seen.as_mut() {
+                if !seen.insert((self.pc, self.registers.clone())) {
+                    return Some(Halt::InfiniteLoop { pc: self.pc });
+                }
+            }
+        }
+        None
+    }
+
+    /// If the computer is at the start of a counting loop, skips to its last iteration.
+    fn fast_forward(&mut self) -> Option<Halt> {
+        let counting_loop = self.counting_loops.as_ref()?.get(&self.pc)?;
+        let step = i128::from(counting_loop.increments[counting_loop.register.0]);
+        // Values that don't fit in an `i128` are too far from 1 to ever become 1.
+        let value = match self.registers[counting_loop.register.0].to_i128() {
+            Some(value) => value,
+            None if step == 0 => return Some(Halt::InfiniteLoop { pc: self.pc }),
+            None => return None,
+        };
+        // Near the ends of an `i128`, just let the loop run instead.
+        let remaining = value
+            .checked_add(i128::from(counting_loop.before_test))
+            .and_then(|value| 1i128.checked_sub(value))?;
+        let mut iterations = match step {
+            0 if remaining == 0 => 0,
+            0 => return Some(Halt::InfiniteLoop { pc: self.pc }),
+            _ if remaining % step == 0 && remaining / step >= 0 => remaining / step,
+            _ => return Some(Halt::InfiniteLoop { pc: self.pc }),
+        };
+        if let Some(limit) = self.step_limit {
+            let allowed = (limit - self.steps) / counting_loop.steps;
+            iterations = iterations.min(allowed as i128);
+        }
+        let registers = self
+            .registers
+            .iter()
+            .zip(counting_loop.increments.iter())
+            .map(|(value, &increment)| {
+                value.checked_add_i128(iterations.checked_mul(i128::from(increment))?)
+            })
+            .collect::<Option<Vec<_>>>();
+        // If a register would overflow, just let the loop run until it does.
+        if let Some(registers) = registers {
+            self.registers = registers;
+            let steps = usize::try_from(iterations)
+                .ok()
+                .and_then(|iterations| iterations.checked_mul(counting_loop.steps));
+            self.steps = steps.map_or(usize::MAX, |steps| self.steps.saturating_add(steps));
+        }
+        None
+    }
+
+    fn jump(&mut self, offset: isize) -> Option<Halt> {
+        match (self.pc as isize).checked_add(offset) {
+            Some(pc) if pc >= 0 => {
+                self.pc = pc as usize;
+                None
+            }
+            _ => Some(Halt::JumpedNegative {
+                pc: self.pc,
+                offset,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use super::super::input_generator;
+    use super::*;
+
+    #[test]
+    fn test_halt() {
+        let program = input_generator("inc b\njmp -2").unwrap();
+        let mut computer = Computer::<i32>::new(program.clone(), 2).unwrap();
+        assert_eq!(computer.run(), Halt::JumpedNegative { pc: 1, offset: -2 });
+        assert_eq!(computer.pc(), 1);
+        assert_eq!(computer.steps(), 2);
+
+        let program = input_generator("inc a\njmp -1").unwrap();
+        let mut computer = Computer::<i32>::new(program, 1)
+            .unwrap()
+            .with_step_limit(10);
+        assert_eq!(computer.run(), Halt::StepLimit { steps: 10 });
+        assert_eq!(*computer.register(Register::A), 5);
+
+        let program = input_generator("inc a\ninc c").unwrap();
+        let mut computer = Computer::<i32>::new(program, 2).unwrap();
+        assert_eq!(
+            computer.run(),
+            Halt::InvalidRegister {
+                pc: 1,
+                register: Register(2)
+            }
+        );
+        let mut computer =
+            Computer::<i32>::new(input_generator("inc a\ninc c").unwrap(), 3).unwrap();
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(computer.registers(), &[1, 0, 1]);
+    }
+
+    #[test]
+    fn test_loop_detection() {
+        let program = input_generator("inc a\nhlf a\njmp -2").unwrap();
+        let mut computer = Computer::<i32>::new(program.clone(), 1)
+            .unwrap()
+            .with_loop_detection();
+        assert_eq!(computer.run(), Halt::InfiniteLoop { pc: 0 });
+        assert_eq!(computer.steps(), 6);
+        let mut computer = Computer::<i32>::new(program, 1)
+            .unwrap()
+            .with_step_limit(100);
+        assert_eq!(computer.run(), Halt::StepLimit { steps: 100 });
+
+        let program = input_generator("jmp +0").unwrap();
+        let mut computer = Computer::<i32>::new(program, 0)
+            .unwrap()
+            .with_loop_detection();
+        assert_eq!(computer.run(), Halt::InfiniteLoop { pc: 0 });
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let program = input_generator("jio a, +4\ninc a\ninc b\njmp -3").unwrap();
+        let mut computer = Computer::<i32>::new(program.clone(), 2)
+            .unwrap()
+            .with_fast_forward();
+        computer.set_register(Register::A, -1_000_000_000);
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(computer.registers(), &[1, 1_000_000_001]);
+        assert_eq!(computer.steps(), 4 * 1_000_000_001 + 1);
+
+        let mut computer = Computer::<i32>::new(program.clone(), 2)
+            .unwrap()
+            .with_fast_forward()
+            .with_step_limit(10);
+        computer.set_register(Register::A, -1_000_000_000);
+        assert_eq!(computer.run(), Halt::StepLimit { steps: 10 });
+        assert_eq!(computer.registers(), &[-999_999_997, 2]);
+
+        let mut computer = Computer::<i32>::new(program, 2)
+            .unwrap()
+            .with_fast_forward();
+        computer.set_register(Register::A, 5);
+        assert_eq!(computer.run(), Halt::InfiniteLoop { pc: 0 });
+
+        // Not a counting loop: `b` isn't incremented when `a` is even.
+        let program = input_generator("jio a, +5\ninc a\njie a, +2\ninc b\njmp -4").unwrap();
+        let mut computer = Computer::<i32>::new(program, 2)
+            .unwrap()
+            .with_fast_forward();
+        computer.set_register(Register::A, -5);
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(computer.registers(), &[1, 3]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Computes 3^40
+        let program = input_generator(&format!("inc a\n{}", "tpl a\n".repeat(40))).unwrap();
+        let mut computer = Computer::<i32>::new(program.clone(), 1).unwrap();
+        assert_eq!(
+            computer.run(),
+            Halt::Overflow {
+                pc: 20,
+                register: Register::A
+            }
+        );
+        assert_eq!(*computer.register(Register::A), 3i32.pow(19));
+        let mut computer = Computer::<u128>::new(program.clone(), 1).unwrap();
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(*computer.register(Register::A), 3u128.pow(40));
+
+        let program = input_generator(&format!("inc a\n{}", "tpl a\n".repeat(100))).unwrap();
+        let mut computer = Computer::<BigInt>::new(program, 1).unwrap();
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(*computer.register(Register::A), BigInt::from(3).pow(100));
+
+        let program = input_generator("inc a\njmp -1").unwrap();
+        let mut computer = Computer::<u32>::new(program, 1)
+            .unwrap()
+            .with_fast_forward();
+        computer.set_register(Register::A, u32::MAX - 2);
+        assert_eq!(
+            computer.run(),
+            Halt::Overflow {
+                pc: 0,
+                register: Register::A
+            }
+        );
+    }
+
+    #[test]
+    fn test_fast_forward_big_values() {
+        let program = input_generator("jio a, +4\ninc a\ninc b\njmp -3").unwrap();
+        let mut computer = Computer::<i64>::new(program.clone(), 2)
+            .unwrap()
+            .with_fast_forward();
+        computer.set_register(Register::A, -1_000_000_000_000);
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(computer.registers(), &[1, 1_000_000_000_001]);
+
+        let mut computer = Computer::<BigInt>::new(program, 2)
+            .unwrap()
+            .with_fast_forward();
+        computer.set_register(Register::A, -BigInt::from(10).pow(30));
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        assert_eq!(
+            *computer.register(Register::B),
+            BigInt::from(10).pow(30) + 1
+        );
+        assert_eq!(computer.steps(), usize::MAX);
+
+        // `1 - a` doesn't fit in an `i128`, so this steps through the loop instead.
+        let program = input_generator("jio a, +4\ninc a\ninc b\njmp -3").unwrap();
+        let mut computer = Computer::<i128>::new(program, 2)
+            .unwrap()
+            .with_fast_forward()
+            .with_step_limit(10);
+        computer.set_register(Register::A, i128::MIN);
+        assert_eq!(computer.run(), Halt::StepLimit { steps: 10 });
+        assert_eq!(computer.registers(), &[i128::MIN + 3, 2]);
+    }
+
+    #[test]
+    fn test_trace() {
+        let program = input_generator("inc a\njio a, +2\ntpl a\ninc a").unwrap();
+        let mut computer = Computer::<i32>::new(program, 2).unwrap().with_trace();
+        assert_eq!(computer.run(), Halt::RanOffEnd);
+        let trace = computer
+            .trace()
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trace,
+            vec![
+                "   0  inc a      a=0 b=0",
+                "   1  jio a, +2  a=1 b=0",
+                "   3  inc a      a=1 b=0",
+            ]
+        );
+        assert!(Computer::<i32>::new(vec![], 2).unwrap().trace().is_empty());
+    }
+
+    #[test]
+    fn test_too_many_registers() {
+        let halt = Computer::<i32>::new(vec![], MAX_REGISTERS + 1).unwrap_err();
+        assert_eq!(halt, Halt::TooManyRegisters { registers: 27 });
+        assert_eq!(
+            halt.to_string(),
+            "27 registers requested, but at most 26 are supported"
+        );
+        assert!(Computer::<i32>::new(vec![], MAX_REGISTERS).is_ok());
+    }
+}
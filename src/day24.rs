@@ -1,138 +1,96 @@
-use std::iter::{empty, once};
+use crate::util::{
+    min_subset_with_sum, parse_lines, parse_number, subset, subsets_with_sum, ParseError,
+};
 
 #[aoc_generator(day24)]
-pub fn input_generator(input: &str) -> Vec<i32> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines(input, |line| parse_number(line, line))
 }
 
-fn split_into_groups_of_sum_and_length(
-    weights: &[i32],
-    sum: i32,
-    group_length: usize,
-) -> Box<dyn Iterator<Item = (Vec<i32>, Vec<i32>)> + '_> {
-    if group_length == 0 {
-        if sum == 0 {
-            Box::new(once((Vec::new(), weights.to_vec())))
-        } else {
-            Box::new(empty())
-        }
-    } else if group_length == weights.len() {
-        if sum == weights.iter().sum::<i32>() {
-            Box::new(once((weights.to_vec(), Vec::new())))
-        } else {
-            Box::new(empty())
-        }
-    } else if group_length > weights.len() {
-        Box::new(empty())
-    } else {
-        debug_assert!(group_length > 0);
-        debug_assert!(group_length < weights.len());
-        let (&first, rest) = weights.split_first().unwrap();
-        if first <= sum {
-            let first_in_group =
-                split_into_groups_of_sum_and_length(rest, sum - first, group_length - 1).map(
-                    move |(mut group, rest)| {
-                        group.push(first);
-                        (group, rest)
-                    },
-                );
-            let first_in_rest = split_into_groups_of_sum_and_length(rest, sum, group_length).map(
-                move |(group, mut rest)| {
-                    rest.push(first);
-                    (group, rest)
-                },
-            );
-            Box::new(first_in_group.chain(first_in_rest))
-        } else {
-            Box::new(empty())
-        }
-    }
+fn quantum_entanglement(weights: &[i32]) -> i64 {
+    weights.iter().map(|&x| x as i64).product::<i64>()
 }
 
-fn split_into_groups_of_sum(
-    weights: &[i32],
-    sum: i32,
-) -> impl Iterator<Item = (Vec<i32>, Vec<i32>)> + '_ {
-    (1..=weights.len())
-        .flat_map(move |group_length| {
-            split_into_groups_of_sum_and_length(weights, sum, group_length)
-        })
-        .map(move |(group, rest)| {
-            assert_eq!(group.iter().sum::<i32>(), sum);
-            (group, rest)
-        })
+/// Splits the packages in `rest` into `groups` groups, out of `subsets`: the subsets of packages
+/// that weigh as much as a group, when `rest` weighs as much as `groups` groups.
+fn split_into_groups(subsets: &[u64], rest: u64, groups: usize) -> Option<Vec<u64>> {
+    match groups {
+        0 if rest == 0 => Some(vec![]),
+        0 => None,
+        1 => Some(vec![rest]),
+        _ => {
+            // Every package goes in some group, so it's enough to try the groups with the first.
+            let first = rest & rest.wrapping_neg();
+            subsets
+                .iter()
+                .filter(|&&group| group & rest == group && group & first != 0)
+                .find_map(|&group| {
+                    let mut split = split_into_groups(subsets, rest & !group, groups - 1)?;
+                    split.insert(0, group);
+                    Some(split)
+                })
+        }
+    }
 }
 
-fn can_split_into_two_groups_of_sum(weights: &[i32], sum: i32) -> bool {
-    split_into_groups_of_sum(weights, sum).next().is_some()
+/// Splits the weights into `groups` groups of equal weight, where the first group has as few
+/// packages as possible. Among the first groups with that few packages, the one with the lowest
+/// `tie_break` wins.
+///
+/// Returns every group, each sorted from the lightest package to the heaviest.
+pub fn partition_by<K, F>(weights: &[i32], groups: usize, mut tie_break: F) -> Option<Vec<Vec<i32>>>
+where
+    K: Ord,
+    F: FnMut(&[i32]) -> K,
+{
+    if groups == 0 {
+        return Some(vec![]).filter(|_| weights.is_empty());
+    }
+    let total = weights.iter().sum::<i32>();
+    if total % groups as i32 != 0 {
+        return None;
+    }
+    let sum = i64::from(total / groups as i32);
+    let subsets = subsets_with_sum(weights, sum);
+    let all = (1 << weights.len()) - 1;
+    let first = min_subset_with_sum(
+        weights,
+        sum,
+        |group| tie_break(&subset(weights, group)),
+        |group| group != 0 && split_into_groups(&subsets, all & !group, groups - 1).is_some(),
+    )?;
+    let split = split_into_groups(&subsets, all & !first, groups - 1)?;
+    let partition = Some(first).into_iter().chain(split).map(|group| {
+        let mut group = subset(weights, group);
+        group.sort_unstable();
+        group
+    });
+    Some(partition.collect())
 }
 
-fn quantum_entanglement(weights: &[i32]) -> i64 {
-    weights.iter().map(|&x| x as i64).product::<i64>()
+/// Splits the weights into `groups` groups of equal weight, where the first group has as few
+/// packages as possible, and then the lowest quantum entanglement.
+pub fn partition(weights: &[i32], groups: usize) -> Option<Vec<Vec<i32>>> {
+    partition_by(weights, groups, quantum_entanglement)
 }
 
 #[aoc(day24, part1)]
 pub fn part1(input: &[i32]) -> i64 {
-    let group_weight = input.iter().sum::<i32>() / 3;
-    for group_length in 1..=input.len() {
-        let best = split_into_groups_of_sum_and_length(input, group_weight, group_length)
-            .filter(|(first_group, rest)| {
-                // First group must have correct sum
-                first_group.iter().sum::<i32>() == group_weight
-                // Must be able to split other weights into two groups with same sum
-               && can_split_into_two_groups_of_sum(rest, group_weight)
-            })
-            .min_by_key(|(first_group, _)| quantum_entanglement(first_group));
-        if let Some((first_group, _)) = best {
-            return quantum_entanglement(&first_group);
-        }
-    }
-    panic!("no solution found")
-}
-
-fn can_split_into_three_groups_of_sum(weights: &[i32], sum: i32) -> bool {
-    split_into_groups_of_sum(weights, sum)
-        .any(|(_, rest)| can_split_into_two_groups_of_sum(&rest, sum))
+    let groups = partition(input, 3).expect("no solution found");
+    quantum_entanglement(&groups[0])
 }
 
 #[aoc(day24, part2)]
 pub fn part2(input: &[i32]) -> i64 {
-    let group_weight = input.iter().sum::<i32>() / 4;
-    for group_length in 1..=input.len() {
-        let best = split_into_groups_of_sum_and_length(input, group_weight, group_length)
-            .filter(|(first_group, rest)| {
-                // First group must have correct sum
-                first_group.iter().sum::<i32>() == group_weight
-                    // Must be able to split other weights into three groups with same sum
-                    && can_split_into_three_groups_of_sum(rest, group_weight)
-            })
-            .min_by_key(|(first_group, _)| quantum_entanglement(first_group));
-        if let Some((first_group, _)) = best {
-            return quantum_entanglement(&first_group);
-        }
-    }
-    panic!("no solution found")
+    let groups = partition(input, 4).expect("no solution found");
+    quantum_entanglement(&groups[0])
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::cmp::Reverse;
 
-    #[test]
-    fn test_split_into_group() {
-        assert_eq!(
-            split_into_groups_of_sum_and_length(&[1, 2, 3], 3, 1).collect::<Vec<_>>(),
-            vec![(vec![3], vec![2, 1]),]
-        );
-        assert_eq!(
-            split_into_groups_of_sum_and_length(&[1, 2, 3], 3, 2).collect::<Vec<_>>(),
-            vec![(vec![2, 1], vec![3]),]
-        );
-        assert_eq!(
-            split_into_groups_of_sum_and_length(&[1, 2, 3], 6, 3).collect::<Vec<_>>(),
-            vec![(vec![1, 2, 3], vec![])]
-        );
-    }
+    use super::*;
 
     #[test]
     fn test_part1() {
@@ -145,4 +103,39 @@ mod tests {
         let input = (1..=5).chain(7..=11).collect::<Vec<i32>>();
         assert_eq!(part2(&input), 44);
     }
+
+    #[test]
+    fn test_partition() {
+        let input = (1..=5).chain(7..=11).collect::<Vec<i32>>();
+        let groups = partition(&input, 3).unwrap();
+        assert_eq!(groups[0], vec![9, 11]);
+        assert_eq!(groups.len(), 3);
+        let mut all = groups.concat();
+        all.sort_unstable();
+        assert_eq!(all, input);
+        assert!(groups.iter().all(|group| group.iter().sum::<i32>() == 20));
+
+        let groups = partition(&input, 4).unwrap();
+        assert_eq!(groups[0], vec![4, 11]);
+        assert_eq!(groups.len(), 4);
+        assert!(groups.iter().all(|group| group.iter().sum::<i32>() == 15));
+
+        // The largest quantum entanglement among the smallest groups instead.
+        let groups = partition_by(&input, 4, |group| Reverse(quantum_entanglement(group))).unwrap();
+        assert_eq!(groups[0], vec![7, 8]);
+
+        assert_eq!(partition(&input, 1), Some(vec![input.clone()]));
+        assert_eq!(partition(&input, 7), None);
+        assert_eq!(partition(&[3, 3, 3, 1, 2], 3), None);
+        assert_eq!(partition(&[], 0), Some(vec![]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("1\n2\n-"),
+            "3:1: invalid number `-`: invalid digit found in string"
+        );
+    }
 }
@@ -1,15 +1,17 @@
+use crate::util::{parse_number, split_once, strip_prefix, strip_suffix, ParseError};
+
 type Input = (u32, u32);
 
 #[aoc_generator(day25)]
-pub fn input_generator(input: &str) -> Input {
-    let s = input
-        .strip_prefix(
-            "To continue, please consult the code grid in the manual.  Enter the code at row ",
-        )
-        .unwrap();
-    let (row, s) = s.split_once(", column ").unwrap();
-    let column = s.strip_suffix('.').unwrap();
-    (row.parse().unwrap(), column.parse().unwrap())
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let s = strip_prefix(
+        input,
+        input,
+        "To continue, please consult the code grid in the manual.  Enter the code at row ",
+    )?;
+    let (row, s) = split_once(input, s, ", column ")?;
+    let column = strip_suffix(input, s, ".")?;
+    Ok((parse_number(input, row)?, parse_number(input, column)?))
 }
 
 fn code_position(row: u32, column: u32) -> u32 {
@@ -54,4 +56,11 @@ mod tests {
         assert_eq!(part1(&(6, 1)), 33071741);
         assert_eq!(part1(&(6, 6)), 27995004);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("To continue, please consult the code grid in the manual.  Enter the code at row 2947, column 3029"), "1:98: expected `.`, found end of line");
+        assert_eq!(error("To continue, please consult the code grid in the manual.  Enter the code at row x, column 3029."), "1:81: invalid number `x`: invalid digit found in string");
+    }
 }
@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 
-use crate::util::Vector2D;
+use crate::util::{ParseError, Vector2D};
 
+#[derive(Debug)]
 pub enum Direction {
     North,
     East,
@@ -21,15 +22,19 @@ impl Direction {
 }
 
 #[aoc_generator(day3)]
-pub fn input_generator(input: &str) -> Vec<Direction> {
+pub fn input_generator(input: &str) -> Result<Vec<Direction>, ParseError> {
     input
-        .chars()
-        .map(|c| match c {
-            '^' => Direction::North,
-            '>' => Direction::East,
-            'v' => Direction::South,
-            '<' => Direction::West,
-            c => panic!("unexpected input: {}", c),
+        .char_indices()
+        .map(|(i, c)| match c {
+            '^' => Ok(Direction::North),
+            '>' => Ok(Direction::East),
+            'v' => Ok(Direction::South),
+            '<' => Ok(Direction::West),
+            _ => Err(ParseError::unknown(
+                input,
+                &input[i..i + c.len_utf8()],
+                "direction",
+            )),
         })
         .collect()
 }
@@ -76,21 +81,27 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = input_generator(">");
+        let input = input_generator(">").unwrap();
         assert_eq!(part1(&input), 2);
-        let input = input_generator("^>v<");
+        let input = input_generator("^>v<").unwrap();
         assert_eq!(part1(&input), 4);
-        let input = input_generator("^v^v^v^v^v");
+        let input = input_generator("^v^v^v^v^v").unwrap();
         assert_eq!(part1(&input), 2);
     }
 
     #[test]
     fn test_part2() {
-        let input = input_generator("^v");
+        let input = input_generator("^v").unwrap();
         assert_eq!(part2(&input), 3);
-        let input = input_generator("^>v<");
+        let input = input_generator("^>v<").unwrap();
         assert_eq!(part2(&input), 3);
-        let input = input_generator("^v^v^v^v^v");
+        let input = input_generator("^v^v^v^v^v").unwrap();
         assert_eq!(part2(&input), 11);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("^>x<"), "1:3: unknown direction `x`");
+    }
 }
@@ -1,6 +1,21 @@
+use crate::util::{end_of, ParseError};
+
 #[aoc_generator(day4)]
-pub fn input_generator(input: &str) -> String {
-    input.to_string()
+pub fn input_generator(input: &str) -> Result<String, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::expected(input, end_of(input), "secret key"));
+    }
+    if let Some((i, c)) = input
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_alphanumeric())
+    {
+        return Err(ParseError::unknown(
+            input,
+            &input[i..i + c.len_utf8()],
+            "secret key character",
+        ));
+    }
+    Ok(input.to_string())
 }
 
 fn solve(secret: &str, success: impl Fn(&[u8; 16]) -> bool) -> i32 {
@@ -42,4 +57,11 @@ mod tests {
         assert_eq!(part1("abcdef"), 609043);
         assert_eq!(part1("pqrstuv"), 1048970);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error(""), "1:1: expected secret key, found end of line");
+        assert_eq!(error("abc def"), "1:4: unknown secret key character ` `");
+    }
 }
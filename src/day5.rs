@@ -1,6 +1,17 @@
+use crate::util::{parse_lines, ParseError};
+
 #[aoc_generator(day5)]
-pub fn input_generator(input: &str) -> Vec<String> {
-    input.lines().map(|line| line.to_string()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<String>, ParseError> {
+    parse_lines(input, |line| {
+        match line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            Some((i, c)) => Err(ParseError::unknown(
+                line,
+                &line[i..i + c.len_utf8()],
+                "letter",
+            )),
+            None => Ok(line.to_string()),
+        }
+    })
 }
 
 fn is_nice_part1(s: &str) -> bool {
@@ -61,4 +72,12 @@ mod tests {
         assert!(!is_nice_part2("uurcxstgmygtbstg"));
         assert!(!is_nice_part2("ieodomkazucvgmuy"));
     }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            input_generator("aaa\nabC").unwrap_err().to_string(),
+            "2:3: unknown letter `C`"
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::util::Vector2D;
+use crate::util::{parse_lines, parse_number, split_once, ParseError, Vector2D};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Instruction(Command, Rectangle);
@@ -16,38 +16,46 @@ pub enum Command {
 pub struct Rectangle(Vector2D, Vector2D);
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(if let Some(s) = s.strip_prefix("turn on ") {
-            Instruction(Command::On, s.parse()?)
-        } else if let Some(s) = s.strip_prefix("turn off ") {
-            Instruction(Command::Off, s.parse()?)
-        } else if let Some(s) = s.strip_prefix("toggle ") {
-            Instruction(Command::Toggle, s.parse()?)
+        let (command, rect) = if let Some(rect) = s.strip_prefix("turn on ") {
+            (Command::On, rect)
+        } else if let Some(rect) = s.strip_prefix("turn off ") {
+            (Command::Off, rect)
+        } else if let Some(rect) = s.strip_prefix("toggle ") {
+            (Command::Toggle, rect)
         } else {
-            panic!("unknown instruction: {}", s);
-        })
+            return Err(ParseError::expected(
+                s,
+                s,
+                "`turn on`, `turn off` or `toggle`",
+            ));
+        };
+        let rect = rect
+            .parse()
+            .map_err(|err: ParseError| err.offset(s, rect))?;
+        Ok(Instruction(command, rect))
     }
 }
 
 impl FromStr for Rectangle {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (top_left, bottom_right) = s.split_once(" through ").unwrap();
-        let (x1, y1) = top_left.split_once(',').unwrap();
-        let (x2, y2) = bottom_right.split_once(',').unwrap();
+        let (top_left, bottom_right) = split_once(s, s, " through ")?;
+        let (x1, y1) = split_once(s, top_left, ",")?;
+        let (x2, y2) = split_once(s, bottom_right, ",")?;
         Ok(Rectangle(
-            Vector2D::new(x1.parse().unwrap(), y1.parse().unwrap()),
-            Vector2D::new(x2.parse().unwrap(), y2.parse().unwrap()),
+            Vector2D::new(parse_number(s, x1)?, parse_number(s, y1)?),
+            Vector2D::new(parse_number(s, x2)?, parse_number(s, y2)?),
         ))
     }
 }
 
 #[aoc_generator(day6)]
-pub fn input_generator(input: &str) -> Vec<Instruction> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input, str::parse)
 }
 
 #[aoc(day6, part1)]
@@ -89,3 +97,21 @@ pub fn part2(input: &[Instruction]) -> u32 {
         .map(|row| IntoIterator::into_iter(row).sum::<u32>())
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("turn on 0,0 through 9,9\nturn of 1,1 through 2,2"),
+            "2:1: expected `turn on`, `turn off` or `toggle`, found `turn`"
+        );
+        assert_eq!(
+            error("toggle 0,0 through 9"),
+            "1:21: expected `,`, found end of line"
+        );
+    }
+}
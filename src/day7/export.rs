@@ -0,0 +1,173 @@
+use std::fmt::Write;
+
+use super::{Circuit, CircuitError, Connection, Gate, Input};
+
+/// The operator of a gate as it appears in the puzzle input, or `None` for `Forward`.
+fn operator<W>(gate: &Gate<W>) -> Option<String> {
+    match gate {
+        Gate::Forward(_) => None,
+        Gate::And(_, _) => Some("AND".to_string()),
+        Gate::Or(_, _) => Some("OR".to_string()),
+        Gate::Not(_) => Some("NOT".to_string()),
+        Gate::LeftShift(_, shift) => Some(format!("LSHIFT {}", shift)),
+        Gate::RightShift(_, shift) => Some(format!("RSHIFT {}", shift)),
+    }
+}
+
+/// Renders the circuit as a Graphviz DOT graph.
+///
+/// Every wire is an ellipse, every gate other than `Forward` a box between its inputs and
+/// its output wire, and every constant input a plain text node.
+pub fn to_dot(connections: &[Connection]) -> String {
+    let mut dot = String::new();
+    dot.push_str("digraph circuit {\n    rankdir=LR;\n    node [shape=ellipse];\n");
+    for Connection(gate, output) in connections {
+        writeln!(dot, "    \"{}\";", output).unwrap();
+        // The node that the inputs connect to: either the gate, or the output wire itself.
+        let target = match operator(gate) {
+            Some(operator) => {
+                let node = format!("{}#gate", output);
+                writeln!(dot, "    \"{}\" [shape=box, label=\"{}\"];", node, operator).unwrap();
+                writeln!(dot, "    \"{}\" -> \"{}\";", node, output).unwrap();
+                node
+            }
+            None => output.clone(),
+        };
+        for (i, input) in gate.inputs().enumerate() {
+            match input {
+                Input::Wire(wire) => writeln!(dot, "    \"{}\" -> \"{}\";", wire, target).unwrap(),
+                Input::Value(value) => {
+                    let node = format!("{}#{}", output, i);
+                    writeln!(
+                        dot,
+                        "    \"{}\" [shape=plaintext, label=\"{}\"];",
+                        node, value
+                    )
+                    .unwrap();
+                    writeln!(dot, "    \"{}\" -> \"{}\";", node, target).unwrap();
+                }
+            }
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// The Verilog identifier of a wire. Wire names are prefixed, so they can't clash with
+/// keywords such as `if`, `or` or `in`.
+fn verilog_wire(wire: &str) -> String {
+    format!("w_{}", wire)
+}
+
+fn verilog_input(input: &Input) -> String {
+    match input {
+        Input::Value(value) => format!("16'd{}", value),
+        Input::Wire(wire) => verilog_wire(wire),
+    }
+}
+
+/// Renders the circuit as a Verilog module with a 16-bit `wire` for every wire, and a
+/// continuous assignment for every gate.
+///
+/// The given `outputs` become the output ports of the module; all other wires are internal.
+/// The circuit has no inputs, as every wire is driven by the circuit itself. Fails if the
+/// circuit isn't valid, or if one of the outputs doesn't exist.
+pub fn to_verilog(
+    connections: &[Connection],
+    module: &str,
+    outputs: &[&str],
+) -> Result<String, CircuitError> {
+    let circuit = Circuit::new(connections)?;
+    for output in outputs {
+        circuit.signal(output)?;
+    }
+    let mut verilog = String::new();
+    let ports = outputs
+        .iter()
+        .map(|output| format!("\n    output wire [15:0] {}", verilog_wire(output)))
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(verilog, "module {}({});", module, ports).unwrap();
+    for Connection(_, output) in connections {
+        if !outputs.contains(&output.as_str()) {
+            writeln!(verilog, "    wire [15:0] {};", verilog_wire(output)).unwrap();
+        }
+    }
+    for Connection(gate, output) in connections {
+        let expression = match gate {
+            Gate::Forward(x) => verilog_input(x),
+            Gate::And(x, y) => format!("{} & {}", verilog_input(x), verilog_input(y)),
+            Gate::Or(x, y) => format!("{} | {}", verilog_input(x), verilog_input(y)),
+            Gate::Not(x) => format!("~{}", verilog_input(x)),
+            Gate::LeftShift(x, shift) => format!("{} << {}", verilog_input(x), shift),
+            Gate::RightShift(x, shift) => format!("{} >> {}", verilog_input(x), shift),
+        };
+        writeln!(
+            verilog,
+            "    assign {} = {};",
+            verilog_wire(output),
+            expression
+        )
+        .unwrap();
+    }
+    verilog.push_str("endmodule\n");
+    Ok(verilog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::input_generator;
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let input = input_generator("123 -> x\nx AND 1 -> y\nNOT y -> z\nx -> a").unwrap();
+        assert_eq!(
+            to_dot(&input),
+            r#"digraph circuit {
+    rankdir=LR;
+    node [shape=ellipse];
+    "x";
+    "x#0" [shape=plaintext, label="123"];
+    "x#0" -> "x";
+    "y";
+    "y#gate" [shape=box, label="AND"];
+    "y#gate" -> "y";
+    "x" -> "y#gate";
+    "y#1" [shape=plaintext, label="1"];
+    "y#1" -> "y#gate";
+    "z";
+    "z#gate" [shape=box, label="NOT"];
+    "z#gate" -> "z";
+    "y" -> "z#gate";
+    "a";
+    "x" -> "a";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_verilog() {
+        let input = input_generator("123 -> x\nx LSHIFT 2 -> if\nNOT if -> a").unwrap();
+        assert_eq!(
+            to_verilog(&input, "day7", &["a"]),
+            Ok("module day7(
+    output wire [15:0] w_a);
+    wire [15:0] w_x;
+    wire [15:0] w_if;
+    assign w_x = 16'd123;
+    assign w_if = w_x << 2;
+    assign w_a = ~w_if;
+endmodule
+"
+            .to_string())
+        );
+        assert_eq!(
+            to_verilog(&input, "day7", &["b"]),
+            Err(CircuitError::Undriven {
+                wire: "b".to_string()
+            })
+        );
+    }
+}
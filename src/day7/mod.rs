@@ -0,0 +1,631 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::mem;
+use std::str::FromStr;
+
+use crate::util::{parse_lines, parse_number, split_once, ParseError};
+
+pub use self::export::*;
+pub use self::optimize::*;
+pub use self::symbolic::*;
+
+mod export;
+mod optimize;
+mod symbolic;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Connection(pub Gate, pub String);
+
+/// A gate, whose inputs are wires of type `W`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Gate<W = String> {
+    Forward(Input<W>),
+    And(Input<W>, Input<W>),
+    Or(Input<W>, Input<W>),
+    Not(Input<W>),
+    LeftShift(Input<W>, u8),
+    RightShift(Input<W>, u8),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Input<W = String> {
+    Value(u16),
+    Wire(W),
+}
+
+impl FromStr for Connection {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (input, output) = split_once(s, s, " -> ")?;
+        if !is_wire(output) {
+            return Err(ParseError::expected(s, output, "output wire"));
+        }
+        let gate = input
+            .parse()
+            .map_err(|err: ParseError| err.offset(s, input))?;
+        Ok(Connection(gate, output.to_string()))
+    }
+}
+
+impl FromStr for Gate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split(' ').collect::<Vec<_>>();
+        let parse_input = |input: &str| {
+            input
+                .parse()
+                .map_err(|err: ParseError| err.offset(s, input))
+        };
+        Ok(match parts[..] {
+            [x] => Gate::Forward(parse_input(x)?),
+            [x, "AND", y] => Gate::And(parse_input(x)?, parse_input(y)?),
+            [x, "OR", y] => Gate::Or(parse_input(x)?, parse_input(y)?),
+            ["NOT", x] => Gate::Not(parse_input(x)?),
+            [x, "LSHIFT", shift] => Gate::LeftShift(parse_input(x)?, parse_number(s, shift)?),
+            [x, "RSHIFT", shift] => Gate::RightShift(parse_input(x)?, parse_number(s, shift)?),
+            [_, op, _] | [op, _] => return Err(ParseError::unknown(s, op, "gate")),
+            _ => return Err(ParseError::expected(s, s, "gate")),
+        })
+    }
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with(|c: char| c.is_ascii_digit()) {
+            Ok(Input::Value(parse_number(s, s)?))
+        } else if is_wire(s) {
+            Ok(Input::Wire(s.to_string()))
+        } else {
+            Err(ParseError::expected(s, s, "wire or value"))
+        }
+    }
+}
+
+fn is_wire(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_lowercase())
+}
+
+#[aoc_generator(day7)]
+pub fn input_generator(input: &str) -> Result<Vec<Connection>, ParseError> {
+    parse_lines(input, str::parse)
+}
+
+impl<W> Gate<W> {
+    /// Returns the inputs of this gate, from left to right.
+    pub fn inputs(&self) -> impl Iterator<Item = &Input<W>> {
+        IntoIterator::into_iter(match self {
+            Gate::Forward(x) | Gate::Not(x) | Gate::LeftShift(x, _) | Gate::RightShift(x, _) => {
+                [Some(x), None]
+            }
+            Gate::And(x, y) | Gate::Or(x, y) => [Some(x), Some(y)],
+        })
+        .flatten()
+    }
+
+    /// Returns the same gate, but with every input wire replaced by `f(wire)`.
+    pub fn map_wires<V>(&self, mut f: impl FnMut(&W) -> V) -> Gate<V> {
+        let mut input = |input: &Input<W>| match input {
+            Input::Value(value) => Input::Value(*value),
+            Input::Wire(wire) => Input::Wire(f(wire)),
+        };
+        match self {
+            Gate::Forward(x) => Gate::Forward(input(x)),
+            Gate::And(x, y) => Gate::And(input(x), input(y)),
+            Gate::Or(x, y) => Gate::Or(input(x), input(y)),
+            Gate::Not(x) => Gate::Not(input(x)),
+            Gate::LeftShift(x, shift) => Gate::LeftShift(input(x), *shift),
+            Gate::RightShift(x, shift) => Gate::RightShift(input(x), *shift),
+        }
+    }
+
+    /// Computes the output of this gate, given the signal on each input wire.
+    pub fn evaluate(&self, mut signal: impl FnMut(&W) -> u16) -> u16 {
+        let mut input = |input: &Input<W>| match input {
+            Input::Value(value) => *value,
+            Input::Wire(wire) => signal(wire),
+        };
+        match self {
+            Gate::Forward(x) => input(x),
+            Gate::And(x, y) => input(x) & input(y),
+            Gate::Or(x, y) => input(x) | input(y),
+            Gate::Not(x) => !input(x),
+            Gate::LeftShift(x, shift) => input(x).checked_shl((*shift).into()).unwrap_or(0),
+            Gate::RightShift(x, shift) => input(x).checked_shr((*shift).into()).unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CircuitError {
+    /// A wire is used, but no connection drives a signal onto it.
+    Undriven { wire: String },
+    /// More than one connection drives a signal onto the same wire.
+    MultipleDrivers { wire: String },
+    /// The signal on these wires depends on itself.
+    Cycle { wires: Vec<String> },
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Undriven { wire } => write!(f, "wire `{}` is not driven", wire),
+            CircuitError::MultipleDrivers { wire } => {
+                write!(f, "wire `{}` is driven by multiple connections", wire)
+            }
+            CircuitError::Cycle { wires } => {
+                write!(
+                    f,
+                    "wires form a cycle: {} -> {}",
+                    wires.join(" -> "),
+                    wires[0]
+                )
+            }
+        }
+    }
+}
+
+impl Error for CircuitError {}
+
+/// A circuit graph, with every wire interned to an index.
+///
+/// The wires are sorted topologically, so the signals on all wires can be computed
+/// in a single pass without recursion.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    /// The name of each wire.
+    names: Vec<String>,
+    /// The index of each wire, by name.
+    indices: HashMap<String, usize>,
+    /// The gate driving each wire.
+    drivers: Vec<Gate<usize>>,
+    /// The wires driven by each wire, i.e. the wires having this wire as (one of) their inputs.
+    fanout: Vec<Vec<usize>>,
+    /// All wires, in an order where every wire comes after the wires it depends on.
+    order: Vec<usize>,
+    /// The position of each wire in `order`.
+    rank: Vec<usize>,
+    /// The signal on each wire.
+    signals: Vec<u16>,
+}
+
+/// A wire whose signal changed after overriding another wire.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SignalChange {
+    pub wire: String,
+    pub old: u16,
+    pub new: u16,
+}
+
+impl Circuit {
+    pub fn new(connections: &[Connection]) -> Result<Self, CircuitError> {
+        let mut indices = HashMap::<String, usize>::new();
+        let mut names = Vec::<String>::new();
+        for Connection(_, output) in connections {
+            if indices.contains_key(output) {
+                return Err(CircuitError::MultipleDrivers {
+                    wire: output.clone(),
+                });
+            }
+            indices.insert(output.clone(), names.len());
+            names.push(output.clone());
+        }
+        let drivers = connections
+            .iter()
+            .map(|Connection(gate, _)| intern(&indices, gate))
+            .collect::<Result<Vec<_>, _>>()?;
+        let fanout = fanout(&drivers);
+        let order = topological_order(&drivers, &fanout).map_err(|cycle| CircuitError::Cycle {
+            wires: cycle.into_iter().map(|wire| names[wire].clone()).collect(),
+        })?;
+        let mut circuit = Self {
+            signals: vec![0; names.len()],
+            rank: vec![0; names.len()],
+            names,
+            indices,
+            drivers,
+            fanout,
+            order: Vec::new(),
+        };
+        circuit.set_order(order);
+        circuit.evaluate();
+        Ok(circuit)
+    }
+
+    fn set_order(&mut self, order: Vec<usize>) {
+        for (rank, &wire) in order.iter().enumerate() {
+            self.rank[wire] = rank;
+        }
+        self.order = order;
+    }
+
+    /// Computes the signals on all wires.
+    fn evaluate(&mut self) {
+        for &wire in self.order.iter() {
+            let signals = &self.signals;
+            self.signals[wire] = self.drivers[wire].evaluate(|&input| signals[input]);
+        }
+    }
+
+    fn index(&self, wire: &str) -> Result<usize, CircuitError> {
+        self.indices
+            .get(wire)
+            .copied()
+            .ok_or_else(|| CircuitError::Undriven {
+                wire: wire.to_string(),
+            })
+    }
+
+    /// Replaces the gate driving `wire`, and updates the signals on all wires downstream of it.
+    ///
+    /// Only the wires whose inputs changed are re-evaluated. Returns the wires whose signal
+    /// changed, in topological order. If the new gate would create a cycle, the circuit is
+    /// left unchanged.
+    pub fn override_wire(
+        &mut self,
+        wire: &str,
+        gate: Gate,
+    ) -> Result<Vec<SignalChange>, CircuitError> {
+        let index = self.index(wire)?;
+        let gate = intern(&self.indices, &gate)?;
+        let old_gate = mem::replace(&mut self.drivers[index], gate);
+        self.update_fanout(index, &old_gate);
+        // If the new gate has an input that doesn't come before the wire itself (including the
+        // wire itself), the current order is no longer valid and we need to sort the wires again.
+        let rank = &self.rank;
+        let needs_sort = self.drivers[index]
+            .inputs()
+            .any(|input| matches!(*input, Input::Wire(input) if rank[input] >= rank[index]));
+        if needs_sort {
+            match topological_order(&self.drivers, &self.fanout) {
+                Ok(order) => self.set_order(order),
+                Err(cycle) => {
+                    let new_gate = mem::replace(&mut self.drivers[index], old_gate);
+                    self.update_fanout(index, &new_gate);
+                    return Err(CircuitError::Cycle {
+                        wires: cycle
+                            .into_iter()
+                            .map(|wire| self.names[wire].clone())
+                            .collect(),
+                    });
+                }
+            }
+        }
+        Ok(self.propagate(index))
+    }
+
+    /// Puts a constant signal onto `wire`, as if it were driven by a `Forward` of that value.
+    pub fn override_signal(
+        &mut self,
+        wire: &str,
+        signal: u16,
+    ) -> Result<Vec<SignalChange>, CircuitError> {
+        self.override_wire(wire, Gate::Forward(Input::Value(signal)))
+    }
+
+    /// Moves `wire` from the fanout of the inputs of `old_gate` to those of its current gate.
+    fn update_fanout(&mut self, wire: usize, old_gate: &Gate<usize>) {
+        for input in old_gate.inputs() {
+            if let Input::Wire(input) = *input {
+                let fanout = &mut self.fanout[input];
+                let position = fanout.iter().position(|&x| x == wire).unwrap();
+                fanout.swap_remove(position);
+            }
+        }
+        for input in self.drivers[wire].inputs() {
+            if let Input::Wire(input) = *input {
+                self.fanout[input].push(wire);
+            }
+        }
+    }
+
+    /// Re-evaluates `wire`, and every wire downstream of it whose inputs changed.
+    fn propagate(&mut self, wire: usize) -> Vec<SignalChange> {
+        let mut changes = Vec::new();
+        let mut queued = vec![false; self.drivers.len()];
+        // Evaluate in topological order, so every wire is evaluated at most once,
+        // after all of its changed inputs have been evaluated.
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((self.rank[wire], wire)));
+        queued[wire] = true;
+        while let Some(Reverse((_, wire))) = queue.pop() {
+            let signals = &self.signals;
+            let signal = self.drivers[wire].evaluate(|&input| signals[input]);
+            let old = mem::replace(&mut self.signals[wire], signal);
+            if old == signal {
+                continue;
+            }
+            changes.push(SignalChange {
+                wire: self.names[wire].clone(),
+                old,
+                new: signal,
+            });
+            for &output in self.fanout[wire].iter() {
+                if !queued[output] {
+                    queued[output] = true;
+                    queue.push(Reverse((self.rank[output], output)));
+                }
+            }
+        }
+        changes
+    }
+
+    /// Returns the names of all wires.
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_str())
+    }
+
+    /// Returns the signal on the given wire.
+    pub fn signal(&self, wire: &str) -> Result<u16, CircuitError> {
+        Ok(self.signals[self.index(wire)?])
+    }
+}
+
+/// Replaces the wire names in `gate` with their indices.
+fn intern(indices: &HashMap<String, usize>, gate: &Gate) -> Result<Gate<usize>, CircuitError> {
+    for input in gate.inputs() {
+        if let Input::Wire(wire) = input {
+            if !indices.contains_key(wire) {
+                return Err(CircuitError::Undriven { wire: wire.clone() });
+            }
+        }
+    }
+    Ok(gate.map_wires(|wire| indices[wire]))
+}
+
+/// Returns the wires driven by each wire.
+fn fanout(drivers: &[Gate<usize>]) -> Vec<Vec<usize>> {
+    let mut fanout = vec![Vec::new(); drivers.len()];
+    for (wire, gate) in drivers.iter().enumerate() {
+        for input in gate.inputs() {
+            if let Input::Wire(input) = *input {
+                fanout[input].push(wire);
+            }
+        }
+    }
+    fanout
+}
+
+/// Sorts the wires topologically, such that every wire comes after the wires driving its gate.
+/// If there is no such order, returns the wires of one of the cycles instead.
+fn topological_order(
+    drivers: &[Gate<usize>],
+    fanout: &[Vec<usize>],
+) -> Result<Vec<usize>, Vec<usize>> {
+    // Kahn's algorithm
+    let mut pending = drivers
+        .iter()
+        .map(|gate| {
+            gate.inputs()
+                .filter(|input| matches!(input, Input::Wire(_)))
+                .count()
+        })
+        .collect::<Vec<_>>();
+    let mut order = (0..drivers.len())
+        .filter(|&wire| pending[wire] == 0)
+        .collect::<Vec<_>>();
+    let mut next = 0;
+    while next < order.len() {
+        let wire = order[next];
+        next += 1;
+        for &output in fanout[wire].iter() {
+            pending[output] -= 1;
+            if pending[output] == 0 {
+                order.push(output);
+            }
+        }
+    }
+    if order.len() == drivers.len() {
+        return Ok(order);
+    }
+    // Every wire that is still pending has at least one pending input.
+    // Keep following those inputs until we end up at a wire we've already seen.
+    let mut wire = (0..drivers.len()).find(|&wire| pending[wire] > 0).unwrap();
+    let mut path = Vec::new();
+    while !path.contains(&wire) {
+        path.push(wire);
+        wire = drivers[wire]
+            .inputs()
+            .find_map(|input| match *input {
+                Input::Wire(input) if pending[input] > 0 => Some(input),
+                _ => None,
+            })
+            .unwrap();
+    }
+    let start = path.iter().position(|&x| x == wire).unwrap();
+    // The path follows inputs backwards, so reverse it to get the direction of the signals.
+    let mut cycle = path.split_off(start);
+    cycle.reverse();
+    // Start the cycle at the wire that was declared first, to make it deterministic.
+    let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(first);
+    Err(cycle)
+}
+
+#[aoc(day7, part1)]
+pub fn part1(input: &[Connection]) -> Result<u16, CircuitError> {
+    let circuit = Circuit::new(input)?;
+    circuit.signal("a")
+}
+
+#[aoc(day7, part2)]
+pub fn part2(input: &[Connection]) -> Result<u16, CircuitError> {
+    let mut circuit = Circuit::new(input)?;
+    // Override wire "b" with the signal on wire "a", and see what "a" becomes now.
+    let a_value = circuit.signal("a")?;
+    circuit.override_signal("b", a_value)?;
+    circuit.signal("a")
+}
+
+#[cfg(test)]
+mod tests {
+    use lazy_static::*;
+
+    use super::*;
+
+    lazy_static! {
+        static ref TEST_INPUT: &'static str = r"
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i"
+            .trim();
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let circuit = Circuit::new(&input).unwrap();
+        assert_eq!(circuit.signal("d"), Ok(72));
+        assert_eq!(circuit.signal("e"), Ok(507));
+        assert_eq!(circuit.signal("f"), Ok(492));
+        assert_eq!(circuit.signal("g"), Ok(114));
+        assert_eq!(circuit.signal("h"), Ok(65412));
+        assert_eq!(circuit.signal("i"), Ok(65079));
+        assert_eq!(circuit.signal("x"), Ok(123));
+        assert_eq!(circuit.signal("y"), Ok(456));
+    }
+
+    #[test]
+    fn test_override_signal() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let mut circuit = Circuit::new(&input).unwrap();
+        let changed = |wire: &str, old, new| SignalChange {
+            wire: wire.to_string(),
+            old,
+            new,
+        };
+        let mut changes = circuit.override_signal("x", 0).unwrap();
+        changes.sort_by(|a, b| a.wire.cmp(&b.wire));
+        assert_eq!(
+            changes,
+            vec![
+                changed("d", 72, 0),
+                changed("e", 507, 456),
+                changed("f", 492, 0),
+                changed("h", 65412, 65535),
+                changed("x", 123, 0),
+            ]
+        );
+        assert_eq!(circuit.signal("g"), Ok(114));
+        // The next override starts from the signals of the last one.
+        changes = circuit.override_signal("y", 4).unwrap();
+        changes.sort_by(|a, b| a.wire.cmp(&b.wire));
+        assert_eq!(
+            changes,
+            vec![
+                changed("e", 456, 4),
+                changed("g", 114, 1),
+                changed("i", 65079, 65531),
+                changed("y", 456, 4),
+            ]
+        );
+        assert_eq!(circuit.signal("d"), Ok(0));
+        // Overriding with the same signal changes nothing.
+        assert_eq!(circuit.override_signal("y", 4), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_override_wire() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let mut circuit = Circuit::new(&input).unwrap();
+        // "i" comes after "x", so this needs a new order.
+        circuit
+            .override_wire("x", Gate::Not(Input::Wire("i".to_string())))
+            .unwrap();
+        assert_eq!(circuit.signal("x"), Ok(456));
+        assert_eq!(circuit.signal("d"), Ok(456));
+        assert_eq!(circuit.signal("h"), Ok(!456));
+        // "x" now depends on "y", so "y" cannot depend on "x".
+        let err = circuit
+            .override_wire("y", Gate::Forward(Input::Wire("h".to_string())))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "wires form a cycle: x -> h -> y -> i -> x");
+        assert_eq!(circuit.signal("y"), Ok(456));
+        // Every wire depends on "y" now.
+        assert_eq!(circuit.override_signal("y", 1).unwrap().len(), input.len());
+        assert_eq!(circuit.signal("h"), Ok(!1));
+    }
+
+    #[test]
+    fn test_override_wire_self_loop() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let mut circuit = Circuit::new(&input).unwrap();
+        let signals = |circuit: &Circuit| {
+            circuit
+                .wires()
+                .map(|wire| (wire.to_string(), circuit.signal(wire).unwrap()))
+                .collect::<Vec<_>>()
+        };
+        let before = signals(&circuit);
+        let x = || Input::Wire("x".to_string());
+        for gate in [Gate::Forward(x()), Gate::Not(x())].iter().cloned() {
+            assert_eq!(
+                circuit.override_wire("x", gate),
+                Err(CircuitError::Cycle {
+                    wires: vec!["x".to_string()]
+                })
+            );
+            assert_eq!(signals(&circuit), before);
+        }
+        // The old gate and its fanout are back in place.
+        assert_eq!(circuit.override_signal("x", 0).unwrap().len(), 5);
+        assert_eq!(circuit.signal("h"), Ok(65535));
+    }
+
+    #[test]
+    fn test_circuit_errors() {
+        let input = input_generator("x AND y -> z\n1 -> x").unwrap();
+        assert_eq!(
+            Circuit::new(&input).unwrap_err(),
+            CircuitError::Undriven {
+                wire: "y".to_string()
+            }
+        );
+        let input = input_generator("1 -> x\n2 -> x").unwrap();
+        assert_eq!(
+            Circuit::new(&input).unwrap_err(),
+            CircuitError::MultipleDrivers {
+                wire: "x".to_string()
+            }
+        );
+        let input = input_generator("1 -> a\nb OR a -> c\nNOT c -> d\nd -> b").unwrap();
+        let err = Circuit::new(&input).unwrap_err();
+        assert_eq!(err.to_string(), "wires form a cycle: c -> d -> b -> c");
+    }
+
+    #[test]
+    fn test_deep_circuit() {
+        // A long chain of forwards would overflow the stack with a recursive evaluation.
+        let mut input = vec![Connection(Gate::Forward(Input::Value(1)), "w0".to_string())];
+        for i in 1..100_000 {
+            let gate = Gate::Not(Input::Wire(format!("w{}", i - 1)));
+            input.push(Connection(gate, format!("w{}", i)));
+        }
+        input.reverse();
+        let circuit = Circuit::new(&input).unwrap();
+        assert_eq!(circuit.signal("w99999"), Ok(!1));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_generator("123 -> x\nx XOR y -> z").unwrap_err();
+        assert_eq!(err.to_string(), "2:3: unknown gate `XOR`");
+        let err = input_generator("x LSHIFT 99999 -> y").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:10: invalid number `99999`: number too large to fit in target type"
+        );
+        let err = input_generator("NOT x y").unwrap_err();
+        assert_eq!(err.to_string(), "1:8: expected `->`, found end of line");
+    }
+}
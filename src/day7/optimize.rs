@@ -0,0 +1,208 @@
+use std::fmt::{Display, Formatter};
+
+use super::{Circuit, CircuitError, Connection, Gate, Input};
+
+/// What [`optimize`] removed from a circuit. Every removed wire is counted exactly once.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct OptimizationStats {
+    /// Wires with a constant signal, which was folded into the gates using them.
+    pub folded: usize,
+    /// Wires that only forwarded another wire, which now feeds their gates directly.
+    pub collapsed: usize,
+    /// Wires that the output doesn't depend on.
+    pub dead: usize,
+}
+
+impl Display for OptimizationStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "folded {} constant wires, collapsed {} forwarding wires, removed {} dead wires",
+            self.folded, self.collapsed, self.dead
+        )
+    }
+}
+
+/// Replaces the input wires of `gate` by what they were simplified to,
+/// and folds the gate into a constant if all of its inputs are constant.
+fn simplify(gate: &Gate<usize>, replacements: &[Input<usize>]) -> Gate<usize> {
+    let input = |input: &Input<usize>| match input {
+        Input::Value(value) => Input::Value(*value),
+        Input::Wire(wire) => replacements[*wire].clone(),
+    };
+    let gate = match gate {
+        Gate::Forward(x) => Gate::Forward(input(x)),
+        Gate::And(x, y) => Gate::And(input(x), input(y)),
+        Gate::Or(x, y) => Gate::Or(input(x), input(y)),
+        Gate::Not(x) => Gate::Not(input(x)),
+        Gate::LeftShift(x, shift) => Gate::LeftShift(input(x), *shift),
+        Gate::RightShift(x, shift) => Gate::RightShift(input(x), *shift),
+    };
+    if gate.inputs().all(|input| matches!(input, Input::Value(_))) {
+        Gate::Forward(Input::Value(gate.evaluate(|_| unreachable!())))
+    } else {
+        gate
+    }
+}
+
+/// Returns which wires `output` depends on through `gates`, including itself.
+fn reachable_from(gates: &[Gate<usize>], output: usize) -> Vec<bool> {
+    let mut reachable = vec![false; gates.len()];
+    reachable[output] = true;
+    let mut stack = vec![output];
+    while let Some(wire) = stack.pop() {
+        for input in gates[wire].inputs() {
+            if let Input::Wire(input) = *input {
+                if !reachable[input] {
+                    reachable[input] = true;
+                    stack.push(input);
+                }
+            }
+        }
+    }
+    reachable
+}
+
+/// Simplifies the circuit to the wires needed to compute the signal on `output`.
+///
+/// Constant subexpressions are folded, chains of `Forward` gates are collapsed, and every
+/// wire that `output` doesn't depend on is removed. The wires in `inputs` are treated as if
+/// their signal were unknown, e.g. because they will be overridden later: they are kept,
+/// and never folded or collapsed into the gates using them.
+///
+/// The remaining connections are returned in their original order.
+pub fn optimize(
+    connections: &[Connection],
+    output: &str,
+    inputs: &[&str],
+) -> Result<(Vec<Connection>, OptimizationStats), CircuitError> {
+    let circuit = Circuit::new(connections)?;
+    let output = circuit.index(output)?;
+    let mut opaque = vec![false; connections.len()];
+    for input in inputs {
+        opaque[circuit.index(input)?] = true;
+    }
+
+    // What each wire is replaced with in the gates using it: a constant, the wire at the
+    // start of its forwarding chain, or the wire itself.
+    let mut replacements = (0..connections.len()).map(Input::Wire).collect::<Vec<_>>();
+    let mut gates = circuit.drivers.clone();
+    for &wire in circuit.order.iter() {
+        gates[wire] = simplify(&circuit.drivers[wire], &replacements);
+        if let Gate::Forward(input) = &gates[wire] {
+            if !opaque[wire] {
+                replacements[wire] = input.clone();
+            }
+        }
+    }
+
+    // The output depends on fewer wires once constants are folded, so the wires it depended on
+    // before tell dead wires apart from folded and collapsed ones.
+    let reachable = reachable_from(&circuit.drivers, output);
+    let live = reachable_from(&gates, output);
+
+    let mut stats = OptimizationStats::default();
+    let mut optimized = Vec::new();
+    for (wire, gate) in gates.iter().enumerate() {
+        if live[wire] {
+            let gate = gate.map_wires(|&input| circuit.names[input].clone());
+            optimized.push(Connection(gate, circuit.names[wire].clone()));
+        } else if opaque[wire] || !reachable[wire] {
+            stats.dead += 1;
+        } else {
+            match gate {
+                Gate::Forward(Input::Value(_)) => stats.folded += 1,
+                Gate::Forward(Input::Wire(_)) => stats.collapsed += 1,
+                _ => stats.dead += 1,
+            }
+        }
+    }
+    Ok((optimized, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use lazy_static::*;
+
+    use super::super::input_generator;
+    use super::*;
+
+    lazy_static! {
+        static ref TEST_INPUT: &'static str = r"
+123 -> x
+x -> p
+p -> q
+456 -> y
+y RSHIFT 2 -> z
+q AND z -> d
+NOT y -> e
+d OR e -> a
+q LSHIFT 1 -> unused"
+            .trim();
+    }
+
+    #[test]
+    fn test_optimize() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let (optimized, stats) = optimize(&input, "a", &[]).unwrap();
+        let signal = Circuit::new(&input).unwrap().signal("a").unwrap();
+        assert_eq!(
+            optimized,
+            input_generator(&format!("{} -> a", signal)).unwrap()
+        );
+        // Without inputs every signal is constant, but `unused` is still dead rather than folded.
+        assert_eq!(
+            stats,
+            OptimizationStats {
+                folded: 7,
+                collapsed: 0,
+                dead: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_optimize_with_inputs() {
+        let input = input_generator(&TEST_INPUT).unwrap();
+        let (optimized, stats) = optimize(&input, "a", &["x"]).unwrap();
+        assert_eq!(
+            optimized,
+            input_generator("123 -> x\nx AND 114 -> d\nd OR 65079 -> a").unwrap()
+        );
+        assert_eq!(
+            stats,
+            OptimizationStats {
+                folded: 3,
+                collapsed: 2,
+                dead: 1,
+            }
+        );
+        assert_eq!(
+            stats.to_string(),
+            "folded 3 constant wires, collapsed 2 forwarding wires, removed 1 dead wires"
+        );
+        // Both circuits still agree after overriding the input.
+        let mut original = Circuit::new(&input).unwrap();
+        let mut optimized = Circuit::new(&optimized).unwrap();
+        for &signal in [0, 1, 4567, u16::MAX].iter() {
+            original.override_signal("x", signal).unwrap();
+            optimized.override_signal("x", signal).unwrap();
+            assert_eq!(optimized.signal("a"), original.signal("a"));
+        }
+    }
+
+    #[test]
+    fn test_optimize_unreachable_constant() {
+        let input = input_generator("123 -> x\n5 -> lonely\nx -> a").unwrap();
+        let (optimized, stats) = optimize(&input, "a", &[]).unwrap();
+        assert_eq!(optimized, input_generator("123 -> a").unwrap());
+        assert_eq!(
+            stats,
+            OptimizationStats {
+                folded: 1,
+                collapsed: 0,
+                dead: 1,
+            }
+        );
+    }
+}
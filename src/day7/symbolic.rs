@@ -0,0 +1,343 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+
+use super::{Circuit, CircuitError, Connection, Gate, Input};
+
+/// The number of bits on a wire.
+const BITS: usize = 16;
+
+const FALSE: usize = 0;
+const TRUE: usize = 1;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct Node {
+    var: usize,
+    low: usize,
+    high: usize,
+}
+
+/// Reduced ordered binary decision diagrams, all sharing the same nodes.
+///
+/// Every boolean function has exactly one node, so two functions are equivalent
+/// if and only if they are the same node.
+#[derive(Debug, Clone)]
+struct Bdd {
+    nodes: Vec<Node>,
+    unique: HashMap<Node, usize>,
+    and_cache: HashMap<(usize, usize), usize>,
+    not_cache: HashMap<usize, usize>,
+}
+
+impl Bdd {
+    fn new() -> Self {
+        // The terminals have a variable that comes after all other variables.
+        let terminal = |value| Node {
+            var: usize::MAX,
+            low: value,
+            high: value,
+        };
+        Self {
+            nodes: vec![terminal(FALSE), terminal(TRUE)],
+            unique: HashMap::new(),
+            and_cache: HashMap::new(),
+            not_cache: HashMap::new(),
+        }
+    }
+
+    fn node(&mut self, var: usize, low: usize, high: usize) -> usize {
+        if low == high {
+            return low;
+        }
+        let node = Node { var, low, high };
+        let nodes = &mut self.nodes;
+        *self.unique.entry(node).or_insert_with(|| {
+            nodes.push(node);
+            nodes.len() - 1
+        })
+    }
+
+    fn var(&mut self, var: usize) -> usize {
+        self.node(var, FALSE, TRUE)
+    }
+
+    /// Returns the cofactors of `node` for `var` being false and true.
+    fn cofactors(&self, node: usize, var: usize) -> (usize, usize) {
+        let Node { var: v, low, high } = self.nodes[node];
+        if v == var {
+            (low, high)
+        } else {
+            (node, node)
+        }
+    }
+
+    fn and(&mut self, a: usize, b: usize) -> usize {
+        if a == FALSE || b == FALSE {
+            return FALSE;
+        }
+        if a == TRUE || a == b {
+            return b;
+        }
+        if b == TRUE {
+            return a;
+        }
+        let key = (a.min(b), a.max(b));
+        if let Some(&result) = self.and_cache.get(&key) {
+            return result;
+        }
+        let var = self.nodes[a].var.min(self.nodes[b].var);
+        let (a_low, a_high) = self.cofactors(a, var);
+        let (b_low, b_high) = self.cofactors(b, var);
+        let low = self.and(a_low, b_low);
+        let high = self.and(a_high, b_high);
+        let result = self.node(var, low, high);
+        self.and_cache.insert(key, result);
+        result
+    }
+
+    fn not(&mut self, a: usize) -> usize {
+        if a <= TRUE {
+            return TRUE - a;
+        }
+        if let Some(&result) = self.not_cache.get(&a) {
+            return result;
+        }
+        let Node { var, low, high } = self.nodes[a];
+        let low = self.not(low);
+        let high = self.not(high);
+        let result = self.node(var, low, high);
+        self.not_cache.insert(a, result);
+        result
+    }
+
+    fn or(&mut self, a: usize, b: usize) -> usize {
+        let not_a = self.not(a);
+        let not_b = self.not(b);
+        let nor = self.and(not_a, not_b);
+        self.not(nor)
+    }
+
+    /// Returns the variables that the function of `node` depends on.
+    fn support(&self, node: usize) -> BTreeSet<usize> {
+        let mut vars = BTreeSet::new();
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if node <= TRUE || seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            let Node { var, low, high } = self.nodes[node];
+            vars.insert(var);
+            stack.push(low);
+            stack.push(high);
+        }
+        vars
+    }
+}
+
+/// A single bit of an input wire of a [`SymbolicCircuit`], where bit 0 is the least
+/// significant bit.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct InputBit {
+    pub wire: String,
+    pub bit: usize,
+}
+
+impl Display for InputBit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]", self.wire, self.bit)
+    }
+}
+
+/// A circuit where the signal on every wire is a function of the signals on its input wires,
+/// rather than a number.
+///
+/// Each of the 16 bits of a wire is a boolean function of the bits of the input wires.
+/// The drivers of the input wires are ignored.
+#[derive(Debug, Clone)]
+pub struct SymbolicCircuit {
+    bdd: Bdd,
+    inputs: Vec<String>,
+    indices: HashMap<String, usize>,
+    /// The function of each bit of each wire, from the least significant bit up.
+    bits: Vec<[usize; BITS]>,
+}
+
+impl SymbolicCircuit {
+    pub fn new(connections: &[Connection], inputs: &[&str]) -> Result<Self, CircuitError> {
+        let circuit = Circuit::new(connections)?;
+        let mut bdd = Bdd::new();
+        let mut bits = vec![[FALSE; BITS]; connections.len()];
+        for (i, input) in inputs.iter().enumerate() {
+            let wire = circuit.index(input)?;
+            for (bit, node) in bits[wire].iter_mut().enumerate() {
+                // Interleave the inputs, as most gates combine the same bit of their inputs.
+                *node = bdd.var(bit * inputs.len() + i);
+            }
+        }
+        for &wire in circuit.order.iter() {
+            if inputs.contains(&circuit.names[wire].as_str()) {
+                continue;
+            }
+            let input = |input: &Input<usize>| match *input {
+                Input::Value(value) => {
+                    let mut value_bits = [FALSE; BITS];
+                    for (bit, value_bit) in value_bits.iter_mut().enumerate() {
+                        *value_bit = if value >> bit & 1 == 1 { TRUE } else { FALSE };
+                    }
+                    value_bits
+                }
+                Input::Wire(input) => bits[input],
+            };
+            let mut output = [FALSE; BITS];
+            match &circuit.drivers[wire] {
+                Gate::Forward(x) => output = input(x),
+                Gate::And(x, y) => {
+                    let (x, y) = (input(x), input(y));
+                    for bit in 0..BITS {
+                        output[bit] = bdd.and(x[bit], y[bit]);
+                    }
+                }
+                Gate::Or(x, y) => {
+                    let (x, y) = (input(x), input(y));
+                    for bit in 0..BITS {
+                        output[bit] = bdd.or(x[bit], y[bit]);
+                    }
+                }
+                Gate::Not(x) => {
+                    let x = input(x);
+                    for bit in 0..BITS {
+                        output[bit] = bdd.not(x[bit]);
+                    }
+                }
+                Gate::LeftShift(x, shift) => {
+                    let x = input(x);
+                    for bit in usize::from(*shift)..BITS {
+                        output[bit] = x[bit - usize::from(*shift)];
+                    }
+                }
+                Gate::RightShift(x, shift) => {
+                    let x = input(x);
+                    for bit in usize::from(*shift)..BITS {
+                        output[bit - usize::from(*shift)] = x[bit];
+                    }
+                }
+            }
+            bits[wire] = output;
+        }
+        Ok(Self {
+            bdd,
+            inputs: inputs.iter().map(|input| input.to_string()).collect(),
+            indices: circuit.indices,
+            bits,
+        })
+    }
+
+    fn wire_bits(&self, wire: &str) -> Result<&[usize; BITS], CircuitError> {
+        match self.indices.get(wire) {
+            Some(&index) => Ok(&self.bits[index]),
+            None => Err(CircuitError::Undriven {
+                wire: wire.to_string(),
+            }),
+        }
+    }
+
+    /// Returns, for each bit of `wire` from the least significant bit up,
+    /// the input bits that can affect it.
+    pub fn dependencies(&self, wire: &str) -> Result<Vec<BTreeSet<InputBit>>, CircuitError> {
+        let bits = self.wire_bits(wire)?;
+        Ok(bits
+            .iter()
+            .map(|&node| {
+                self.bdd
+                    .support(node)
+                    .into_iter()
+                    .map(|var| InputBit {
+                        wire: self.inputs[var % self.inputs.len()].clone(),
+                        bit: var / self.inputs.len(),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Checks whether two wires carry the same signal, whatever the signals on the inputs are.
+    pub fn equivalent(&self, a: &str, b: &str) -> Result<bool, CircuitError> {
+        Ok(self.wire_bits(a)? == self.wire_bits(b)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::input_generator;
+    use super::*;
+
+    fn input_bits(wire: &str, bits: &[usize]) -> BTreeSet<InputBit> {
+        bits.iter()
+            .map(|&bit| InputBit {
+                wire: wire.to_string(),
+                bit,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let input =
+            input_generator("123 -> x\n456 -> y\nx AND y -> d\nx LSHIFT 2 -> f\nNOT x -> h")
+                .unwrap();
+        let circuit = SymbolicCircuit::new(&input, &["x"]).unwrap();
+        // 456 = 0b111001000
+        let dependencies = circuit.dependencies("d").unwrap();
+        for (bit, dependencies) in dependencies.iter().enumerate() {
+            if [3, 6, 7, 8].contains(&bit) {
+                assert_eq!(*dependencies, input_bits("x", &[bit]));
+            } else {
+                assert!(dependencies.is_empty());
+            }
+        }
+        let dependencies = circuit.dependencies("f").unwrap();
+        assert!(dependencies[1].is_empty());
+        assert_eq!(dependencies[2], input_bits("x", &[0]));
+        assert_eq!(dependencies[15], input_bits("x", &[13]));
+        assert!(circuit.dependencies("y").unwrap()[0].is_empty());
+        assert_eq!(
+            circuit.dependencies("h").unwrap()[0]
+                .iter()
+                .map(|bit| bit.to_string())
+                .collect::<Vec<_>>(),
+            vec!["x[0]"]
+        );
+        assert!(circuit.dependencies("z").is_err());
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let input = input_generator(
+            "1 -> x
+2 -> y
+x AND y -> p
+NOT p -> a
+NOT x -> q
+NOT y -> r
+q OR r -> b
+NOT q -> c
+x LSHIFT 1 -> d
+d RSHIFT 1 -> e
+x AND 32767 -> f
+q AND r -> g",
+        )
+        .unwrap();
+        let circuit = SymbolicCircuit::new(&input, &["x", "y"]).unwrap();
+        // De Morgan
+        assert_eq!(circuit.equivalent("a", "b"), Ok(true));
+        assert_eq!(circuit.equivalent("c", "x"), Ok(true));
+        assert_eq!(circuit.equivalent("e", "f"), Ok(true));
+        assert_eq!(circuit.equivalent("a", "g"), Ok(false));
+        assert_eq!(circuit.equivalent("e", "x"), Ok(false));
+        let dependencies = circuit.dependencies("a").unwrap();
+        let mut expected = input_bits("x", &[4]);
+        expected.extend(input_bits("y", &[4]));
+        assert_eq!(dependencies[4], expected);
+    }
+}
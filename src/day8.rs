@@ -1,39 +1,63 @@
-#[aoc_generator(day8)]
-pub fn input_generator(input: &str) -> Vec<String> {
-    input.lines().map(|line| line.to_string()).collect()
+use crate::util::{end_of, parse_lines, strip_prefix, ParseError};
+
+/// A string literal, as written in the code and as the string it stands for.
+#[derive(Debug)]
+pub struct Literal {
+    code: String,
+    value: Vec<char>,
 }
 
-fn count_unescaped(s: &str) -> usize {
-    let mut count = 0;
-    let mut chars = s.chars();
-    assert_eq!(chars.next().unwrap(), '\"');
-    while let Some(c) = chars.next() {
+/// Parses a string literal like `"aaa\"aaa"`, with `\\`, `\"` and `\x27` escapes.
+fn parse_literal(line: &str) -> Result<Literal, ParseError> {
+    let body = strip_prefix(line, line, "\"")?;
+    let mut value = vec![];
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
         match c {
-            '\\' => match chars.next().unwrap() {
-                'x' => {
-                    // \x00
-                    chars.next().unwrap();
-                    chars.next().unwrap();
+            '\\' => match chars.next() {
+                Some((_, 'x')) => {
+                    let digits = body[i + 2..].get(..2).unwrap_or(&body[i + 2..]);
+                    // `from_str_radix` would also take a sign, like in `\x+1`.
+                    if digits.len() != 2 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        let escape = &body[i..i + 2 + digits.len()];
+                        let reason = "expected two hex digits";
+                        return Err(ParseError::invalid(line, escape, "escape", reason));
+                    }
+                    value.push(char::from(u8::from_str_radix(digits, 16).unwrap()));
+                    chars.nth(1);
+                }
+                Some((_, escaped @ '\\')) | Some((_, escaped @ '"')) => value.push(escaped),
+                Some((j, c)) => {
+                    let escape = &body[i..j + c.len_utf8()];
+                    return Err(ParseError::unknown(line, escape, "escape sequence"));
                 }
-                '\\' | '\"' => {}
-                c => panic!("unexpected escape sequence: \\{}", c),
+                None => return Err(ParseError::expected(line, end_of(line), "escape sequence")),
             },
-            '\"' => {
-                // end of string
-                assert_eq!(chars.next(), None);
-                break;
+            '"' => {
+                let rest = &body[i + 1..];
+                if !rest.is_empty() {
+                    return Err(ParseError::expected(line, rest, "end of line"));
+                }
+                return Ok(Literal {
+                    code: line.to_string(),
+                    value,
+                });
             }
-            _ => {} // regular character
-        };
-        count += 1;
+            c => value.push(c),
+        }
     }
-    count
+    Err(ParseError::expected(line, end_of(line), "`\"`"))
+}
+
+#[aoc_generator(day8)]
+pub fn input_generator(input: &str) -> Result<Vec<Literal>, ParseError> {
+    parse_lines(input, parse_literal)
 }
 
 #[aoc(day8, part1)]
-pub fn part1(input: &[String]) -> usize {
-    let count_raw = input.iter().map(|s| s.len()).sum::<usize>();
-    let count_unescaped = input.iter().map(|s| count_unescaped(s)).sum::<usize>();
+pub fn part1(input: &[Literal]) -> usize {
+    let count_raw = input.iter().map(|s| s.code.len()).sum::<usize>();
+    let count_unescaped = input.iter().map(|s| s.value.len()).sum::<usize>();
     count_raw - count_unescaped
 }
 
@@ -51,9 +75,9 @@ fn count_escaped(s: &str) -> usize {
 }
 
 #[aoc(day8, part2)]
-pub fn part2(input: &[String]) -> usize {
-    let count_raw = input.iter().map(|s| s.len()).sum::<usize>();
-    let count_escaped = input.iter().map(|s| count_escaped(s)).sum::<usize>();
+pub fn part2(input: &[Literal]) -> usize {
+    let count_raw = input.iter().map(|s| s.code.len()).sum::<usize>();
+    let count_escaped = input.iter().map(|s| count_escaped(&s.code)).sum::<usize>();
     count_escaped - count_raw
 }
 
@@ -75,13 +99,42 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part1(&input), 12);
     }
 
     #[test]
     fn test_part2() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part2(&input), 19);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(error("\"\"\nabc\""), "2:1: expected `\"`, found `abc\"`");
+        assert_eq!(error("\"abc"), "1:5: expected `\"`, found end of line");
+        assert_eq!(error("\"a\"bc"), "1:4: expected end of line, found `bc`");
+        assert_eq!(error("\"a\\n\""), "1:3: unknown escape sequence `\\n`");
+        assert_eq!(
+            error("\"\\xz1\""),
+            "1:2: invalid escape `\\xz1`: expected two hex digits"
+        );
+        assert_eq!(
+            error("\"\\x+1\""),
+            "1:2: invalid escape `\\x+1`: expected two hex digits"
+        );
+        assert_eq!(
+            error("\"\\xg1\""),
+            "1:2: invalid escape `\\xg1`: expected two hex digits"
+        );
+        assert_eq!(
+            error("\"\\x1"),
+            "1:2: invalid escape `\\x1`: expected two hex digits"
+        );
+        assert_eq!(
+            error("\"\\"),
+            "1:3: expected escape sequence, found end of line"
+        );
+    }
 }
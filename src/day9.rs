@@ -1,19 +1,18 @@
 use std::collections::HashMap;
 
+use crate::util::{parse_lines, parse_number, split_once, ParseError};
+
 type Link = ((String, String), u32);
 
 #[aoc_generator(day9)]
-pub fn input_generator(input: &str) -> Vec<Link> {
-    input
-        .lines()
-        .map(|line| {
-            // AlphaCentauri to Snowdin = 66
-            let (locations, distance) = line.split_once(" = ").unwrap();
-            let (left, right) = locations.split_once(" to ").unwrap();
-            let distance = distance.parse().unwrap();
-            ((left.to_string(), right.to_string()), distance)
-        })
-        .collect()
+pub fn input_generator(input: &str) -> Result<Vec<Link>, ParseError> {
+    parse_lines(input, |line| {
+        // AlphaCentauri to Snowdin = 66
+        let (locations, distance) = split_once(line, line, " = ")?;
+        let (left, right) = split_once(line, locations, " to ")?;
+        let distance = parse_number(line, distance)?;
+        Ok(((left.to_string(), right.to_string()), distance))
+    })
 }
 
 type LinkMap = HashMap<String, Vec<(String, u32)>>;
@@ -94,13 +93,26 @@ Dublin to Belfast = 141"
 
     #[test]
     fn test_part1() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part1(&input), 605);
     }
 
     #[test]
     fn test_part2() {
-        let input = input_generator(&TEST_INPUT);
+        let input = input_generator(&TEST_INPUT).unwrap();
         assert_eq!(part2(&input), 982);
     }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| input_generator(s).unwrap_err().to_string();
+        assert_eq!(
+            error("London to Dublin = 464\nLondon - Belfast = 518"),
+            "2:17: expected `to`, found end of line"
+        );
+        assert_eq!(
+            error("London to Dublin = far"),
+            "1:20: invalid number `far`: invalid digit found in string"
+        );
+    }
 }
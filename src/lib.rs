@@ -4,6 +4,7 @@ extern crate aoc_runner_derive;
 extern crate itertools;
 extern crate json;
 extern crate lazy_static;
+extern crate num_bigint;
 extern crate regex;
 
 mod day1;
@@ -19,18 +20,19 @@ mod day18;
 mod day19;
 mod day2;
 mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
 mod day25;
 mod day3;
 mod day4;
 mod day5;
 mod day6;
-mod day7;
+pub mod day7;
 mod day8;
 mod day9;
+pub mod runner;
 mod util;
 
 aoc_lib! { year = 2015 }
@@ -1,7 +1,330 @@
 extern crate advent_of_code_2015;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
 
-use aoc_runner_derive::aoc_main;
+use std::env;
+use std::process::{self, Command};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-aoc_main! { lib = advent_of_code_2015 }
+use advent_of_code_2015::runner::{
+    bench, solutions, AnswerStore, BenchHistory, Benchmark, HistoryEntry, InputStore, Solution,
+    Table, Verdict, DEFAULT_PROFILE,
+};
+use advent_of_code_2015::YEAR;
+
+const USAGE: &str = "\
+Usage: advent-of-code-2015 [OPTIONS]
+
+Options:
+    --profile <NAME>   Run against the inputs of this profile (can be repeated)
+    --all-profiles     Run against the inputs of every profile
+    --day <N>          Only run the solutions for this day
+    --verify           Check the answers against the recorded answers in `answers/`
+    --record           Like --verify, but also record answers that are new
+    --bench <RUNS>     Time each generator and solver over this many runs,
+                       and append the results to `bench/history.txt`
+    --help             Print this help";
+
+#[derive(Debug, Default)]
+struct Options {
+    profiles: Vec<String>,
+    all_profiles: bool,
+    day: Option<u32>,
+    verify: bool,
+    record: bool,
+    bench: Option<usize>,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--profile" => options.profiles.push(value()?),
+            "--all-profiles" => options.all_profiles = true,
+            "--day" => {
+                let day = value()?;
+                options.day = Some(day.parse().map_err(|_| format!("invalid day: {}", day))?);
+            }
+            "--verify" => options.verify = true,
+            "--record" => {
+                options.verify = true;
+                options.record = true;
+            }
+            "--bench" => {
+                let runs = value()?;
+                options.bench = match runs.parse() {
+                    Ok(runs) if runs > 0 => Some(runs),
+                    _ => return Err(format!("invalid number of runs: {}", runs)),
+                };
+            }
+            "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("unknown option: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// The outcome of running a single solution against a single profile's input.
+enum Outcome {
+    Answer { input: String, answer: String },
+    Missing,
+    Failed,
+}
+
+fn run(
+    store: &InputStore,
+    profile: &str,
+    solution: &Solution,
+    errors: &mut Vec<String>,
+) -> Outcome {
+    let path = store.path(profile, solution.day);
+    match store.load(profile, solution.day) {
+        Ok(Some(input)) => match solution.run(&input) {
+            Ok(answer) => Outcome::Answer { input, answer },
+            Err(err) => {
+                errors.push(format!("{}: {}", solution, err.describe(&path)));
+                Outcome::Failed
+            }
+        },
+        Ok(None) => Outcome::Missing,
+        Err(err) => {
+            errors.push(format!("{}: {}: {}", solution, path.display(), err));
+            Outcome::Failed
+        }
+    }
+}
+
+/// Prints a table with the answers of every solution, with one column per profile.
+fn print_answers(
+    store: &InputStore,
+    profiles: &[String],
+    solutions: &[Solution],
+    errors: &mut Vec<String>,
+) {
+    let mut header = vec!["Day".to_string(), "Part".to_string()];
+    header.extend(profiles.iter().cloned());
+    let mut table = Table::new(header);
+    for solution in solutions {
+        let mut row = vec![solution.day.to_string(), solution.part.to_string()];
+        for profile in profiles {
+            row.push(match run(store, profile, solution, errors) {
+                Outcome::Answer { answer, .. } => answer,
+                Outcome::Missing => "-".to_string(),
+                Outcome::Failed => "FAILED".to_string(),
+            });
+        }
+        table.push(row);
+    }
+    print!("{}", table);
+}
+
+/// Prints a table with the verdict of every solution against the recorded answers,
+/// and returns whether all answers passed or were new.
+fn verify_answers(
+    store: &InputStore,
+    profiles: &[String],
+    solutions: &[Solution],
+    record: bool,
+    errors: &mut Vec<String>,
+) -> bool {
+    let answer_store = AnswerStore::new("answers", YEAR);
+    let header = ["Day", "Part", "Profile", "Verdict", "Answer", "Expected"];
+    let mut table = Table::new(header.iter().map(|s| s.to_string()).collect());
+    let (mut passed, mut failed, mut new) = (0, 0, 0);
+    for profile in profiles {
+        let mut answers = answer_store
+            .load(profile)
+            .expect("failed to load recorded answers");
+        for solution in solutions {
+            let (verdict, answer, expected) = match run(store, profile, solution, errors) {
+                Outcome::Answer { input, answer } => {
+                    let verdict = answers.check(solution.day, solution.part, &input, &answer);
+                    let expected = match &verdict {
+                        Verdict::Pass => {
+                            passed += 1;
+                            answer.clone()
+                        }
+                        Verdict::Fail { expected } => {
+                            failed += 1;
+                            expected.clone()
+                        }
+                        Verdict::New => {
+                            new += 1;
+                            if record {
+                                answers.record(solution.day, solution.part, &input, &answer);
+                            }
+                            String::new()
+                        }
+                    };
+                    (verdict.to_string(), answer, expected)
+                }
+                Outcome::Missing => continue,
+                Outcome::Failed => {
+                    failed += 1;
+                    ("FAIL".to_string(), "FAILED".to_string(), String::new())
+                }
+            };
+            table.push(vec![
+                solution.day.to_string(),
+                solution.part.to_string(),
+                profile.clone(),
+                verdict,
+                answer,
+                expected,
+            ]);
+        }
+        if record {
+            answer_store
+                .save(profile, &answers)
+                .expect("failed to save recorded answers");
+        }
+    }
+    print!("{}", table);
+    println!(
+        "{} passed, {} failed, {} new{}",
+        passed,
+        failed,
+        new,
+        if record && new > 0 { " (recorded)" } else { "" }
+    );
+    failed == 0
+}
+
+/// Describes the current commit, so benchmark results can be compared between commits.
+fn describe_commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Formats the relative change between two durations, e.g. `+12.5%`.
+fn format_change(old: Duration, new: Duration) -> String {
+    let old = old.as_secs_f64();
+    let new = new.as_secs_f64();
+    if old == 0.0 {
+        return String::new();
+    }
+    format!("{:+.1}%", (new - old) / old * 100.0)
+}
+
+/// Benchmarks every solution, prints a table with the timings compared to the previous
+/// benchmark in the history, and appends the new timings to the history.
+fn bench_solutions(
+    store: &InputStore,
+    profiles: &[String],
+    solutions: &[Solution],
+    runs: usize,
+    errors: &mut Vec<String>,
+) {
+    let history = BenchHistory::new("bench/history.txt");
+    let previous = history.load().expect("failed to load benchmark history");
+    let commit = describe_commit();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let header = [
+        "Day",
+        "Part",
+        "Profile",
+        "Generator (min / median / max)",
+        "Change",
+        "Solver (min / median / max)",
+        "Change",
+    ];
+    let mut table = Table::new(header.iter().map(|s| s.to_string()).collect());
+    let mut entries = Vec::new();
+    for profile in profiles {
+        for solution in solutions {
+            let path = store.path(profile, solution.day);
+            let input = match store.load(profile, solution.day) {
+                Ok(Some(input)) => input,
+                Ok(None) => continue,
+                Err(err) => {
+                    errors.push(format!("{}: {}: {}", solution, path.display(), err));
+                    continue;
+                }
+            };
+            let benchmark = match bench(solution, &input, runs) {
+                Ok(benchmark) => benchmark,
+                Err(err) => {
+                    errors.push(format!("{}: {}", solution, err.describe(&path)));
+                    continue;
+                }
+            };
+            let latest = BenchHistory::latest(&previous, profile, solution.day, solution.part);
+            let change = |stats: fn(&Benchmark) -> Duration| {
+                latest.map_or(String::new(), |latest| {
+                    format_change(stats(&latest.benchmark), stats(&benchmark))
+                })
+            };
+            table.push(vec![
+                solution.day.to_string(),
+                solution.part.to_string(),
+                profile.clone(),
+                benchmark.generator.to_string(),
+                change(|benchmark| benchmark.generator.median),
+                benchmark.solver.to_string(),
+                change(|benchmark| benchmark.solver.median),
+            ]);
+            entries.push(HistoryEntry {
+                timestamp,
+                commit: commit.clone(),
+                profile: profile.clone(),
+                day: solution.day,
+                part: solution.part,
+                benchmark,
+            });
+        }
+    }
+    print!("{}", table);
+    println!("Changes are relative to the median of the previous run in the history.");
+    history
+        .append(&entries)
+        .expect("failed to append to benchmark history");
+}
+
+fn main() {
+    let options = parse_options(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+    let store = InputStore::new("input", YEAR);
+    let profiles = if options.all_profiles {
+        store.profiles().expect("failed to list profiles")
+    } else if options.profiles.is_empty() {
+        vec![DEFAULT_PROFILE.to_string()]
+    } else {
+        options.profiles.clone()
+    };
+    let solutions = solutions()
+        .into_iter()
+        .filter(|solution| options.day.map_or(true, |day| day == solution.day))
+        .collect::<Vec<_>>();
+
+    println!("Advent of code {}", YEAR);
+
+    let mut errors = Vec::new();
+    let success = if let Some(runs) = options.bench {
+        bench_solutions(&store, &profiles, &solutions, runs, &mut errors);
+        true
+    } else if options.verify {
+        verify_answers(&store, &profiles, &solutions, options.record, &mut errors)
+    } else {
+        print_answers(&store, &profiles, &solutions, &mut errors);
+        true
+    };
+
+    for error in errors.iter() {
+        eprintln!("{}", error);
+    }
+    if !success || !errors.is_empty() {
+        process::exit(1);
+    }
+}
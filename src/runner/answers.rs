@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::util::{parse_lines, parse_number, split_once, strip_prefix, ParseError};
+
+/// Recorded answers on disk, one file per profile: `<root>/<year>/<profile>.txt`.
+#[derive(Debug, Clone)]
+pub struct AnswerStore {
+    root: PathBuf,
+    year: u32,
+}
+
+impl AnswerStore {
+    pub fn new(root: impl Into<PathBuf>, year: u32) -> Self {
+        Self {
+            root: root.into(),
+            year,
+        }
+    }
+
+    /// Returns the path to the answers file of the given profile.
+    pub fn path(&self, profile: &str) -> PathBuf {
+        self.root
+            .join(self.year.to_string())
+            .join(format!("{}.txt", profile))
+    }
+
+    /// Loads the answers of the given profile, or no answers at all if there is no such file.
+    pub fn load(&self, profile: &str) -> io::Result<Answers> {
+        let path = self.path(profile);
+        match fs::read_to_string(&path) {
+            Ok(contents) => contents.parse().map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}", path.display(), err),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, profile: &str, answers: &Answers) -> io::Result<()> {
+        let path = self.path(profile);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, answers.to_string())
+    }
+}
+
+/// The recorded answers of a single profile, keyed by day and part.
+///
+/// Every answer remembers a hash of the input it was computed from, so an answer is only
+/// checked against the same input it was recorded for.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32), Answer>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Answer {
+    input_hash: String,
+    answer: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    /// The answer matches the recorded answer.
+    Pass,
+    /// The answer differs from the recorded answer.
+    Fail { expected: String },
+    /// There is no recorded answer for this input yet.
+    New,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { .. } => write!(f, "FAIL"),
+            Verdict::New => write!(f, "NEW"),
+        }
+    }
+}
+
+fn input_hash(input: &str) -> String {
+    format!("{:x}", md5::compute(input.as_bytes()))
+}
+
+impl Answers {
+    /// Checks an answer against the recorded answer for the same day, part and input.
+    pub fn check(&self, day: u32, part: u32, input: &str, answer: &str) -> Verdict {
+        match self.answers.get(&(day, part)) {
+            Some(recorded) if recorded.input_hash == input_hash(input) => {
+                if recorded.answer == answer {
+                    Verdict::Pass
+                } else {
+                    Verdict::Fail {
+                        expected: recorded.answer.clone(),
+                    }
+                }
+            }
+            _ => Verdict::New,
+        }
+    }
+
+    /// Records an answer, replacing any previously recorded answer for the same day and part.
+    pub fn record(&mut self, day: u32, part: u32, input: &str, answer: &str) {
+        self.answers.insert(
+            (day, part),
+            Answer {
+                input_hash: input_hash(input),
+                answer: answer.to_string(),
+            },
+        );
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Example:
+        //   day7 part1 4d3f0e6b2c53a0a6d6ac8e0d0a5f5b09 3176
+        let answers = parse_lines(s, |line| {
+            let rest = strip_prefix(line, line, "day")?;
+            let (day, rest) = split_once(line, rest, " part")?;
+            let (part, rest) = split_once(line, rest, " ")?;
+            let (input_hash, answer) = split_once(line, rest, " ")?;
+            let key = (parse_number(line, day)?, parse_number(line, part)?);
+            let answer = Answer {
+                input_hash: input_hash.to_string(),
+                answer: answer.to_string(),
+            };
+            Ok((key, answer))
+        })?;
+        Ok(Answers {
+            answers: answers.into_iter().collect(),
+        })
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ((day, part), answer) in self.answers.iter() {
+            writeln!(
+                f,
+                "day{} part{} {} {}",
+                day, part, answer.input_hash, answer.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.check(1, 1, "(()", "1"), Verdict::New);
+        answers.record(1, 1, "(()", "1");
+        assert_eq!(answers.check(1, 1, "(()", "1"), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 1, "(()", "2"),
+            Verdict::Fail {
+                expected: "1".to_string()
+            }
+        );
+        // Different input
+        assert_eq!(answers.check(1, 1, "(((", "3"), Verdict::New);
+        // Different part
+        assert_eq!(answers.check(1, 2, "(()", "1"), Verdict::New);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(11, 1, "abcdefgh", "abcdffaa");
+        answers.record(7, 2, "123 -> a", "123");
+        let s = answers.to_string();
+        assert_eq!(s.lines().next().unwrap().split(' ').next(), Some("day7"));
+        assert_eq!(s.parse::<Answers>(), Ok(answers));
+        assert_eq!(
+            "day7 part1 abc".parse::<Answers>().unwrap_err().to_string(),
+            "1:15: expected whitespace, found end of line"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::runner::{Solution, SolutionError};
+use crate::util::{parse_lines, parse_number, ParseError};
+
+/// The minimum, median and maximum of a number of timed runs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len() % 2 == 0 {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2?} / {:.2?} / {:.2?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+/// Timings for the generator and the solver of a single solution.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Benchmark {
+    pub runs: usize,
+    pub generator: Stats,
+    pub solver: Stats,
+}
+
+/// Runs the generator and then the solver of `solution` `runs` times each.
+pub fn bench(solution: &Solution, input: &str, runs: usize) -> Result<Benchmark, SolutionError> {
+    assert!(runs > 0, "must run at least once");
+    let mut generator_samples = Vec::with_capacity(runs);
+    let mut runner = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        let generated = solution.generate(input)?;
+        generator_samples.push(start.elapsed());
+        runner = Some(generated);
+    }
+    let runner = runner.unwrap();
+    let mut solver_samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        solution.solve(runner.as_ref())?;
+        solver_samples.push(start.elapsed());
+    }
+    Ok(Benchmark {
+        runs,
+        generator: Stats::from_samples(generator_samples),
+        solver: Stats::from_samples(solver_samples),
+    })
+}
+
+/// A benchmark result, as recorded in the history file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The commit that was benchmarked, as given by `git describe --always --dirty`.
+    pub commit: String,
+    pub profile: String,
+    pub day: u32,
+    pub part: u32,
+    pub benchmark: Benchmark,
+}
+
+impl FromStr for HistoryEntry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Example (durations are in nanoseconds):
+        //   1634567890 e7a1ae5 default 7 1 10 1200 1250 1900 800 820 1100
+        let fields = s.split(' ').collect::<Vec<_>>();
+        let (timestamp, commit, profile, day, part, runs, durations) = match fields[..] {
+            [timestamp, commit, profile, day, part, runs, ref durations @ ..]
+                if durations.len() == 6 =>
+            {
+                (timestamp, commit, profile, day, part, runs, durations)
+            }
+            _ => return Err(ParseError::expected(s, s, "12 fields")),
+        };
+        let durations = durations
+            .iter()
+            .map(|nanos| parse_number(s, nanos).map(Duration::from_nanos))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HistoryEntry {
+            timestamp: parse_number(s, timestamp)?,
+            commit: commit.to_string(),
+            profile: profile.to_string(),
+            day: parse_number(s, day)?,
+            part: parse_number(s, part)?,
+            benchmark: Benchmark {
+                runs: parse_number(s, runs)?,
+                generator: Stats {
+                    min: durations[0],
+                    median: durations[1],
+                    max: durations[2],
+                },
+                solver: Stats {
+                    min: durations[3],
+                    median: durations[4],
+                    max: durations[5],
+                },
+            },
+        })
+    }
+}
+
+impl Display for HistoryEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Benchmark {
+            runs,
+            generator,
+            solver,
+        } = self.benchmark;
+        write!(
+            f,
+            "{} {} {} {} {} {} {} {} {} {} {} {}",
+            self.timestamp,
+            self.commit,
+            self.profile,
+            self.day,
+            self.part,
+            runs,
+            generator.min.as_nanos(),
+            generator.median.as_nanos(),
+            generator.max.as_nanos(),
+            solver.min.as_nanos(),
+            solver.median.as_nanos(),
+            solver.max.as_nanos()
+        )
+    }
+}
+
+/// An append-only file of benchmark results, one [`HistoryEntry`] per line.
+#[derive(Debug, Clone)]
+pub struct BenchHistory {
+    path: PathBuf,
+}
+
+impl BenchHistory {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Loads all entries, from oldest to newest.
+    pub fn load(&self) -> io::Result<Vec<HistoryEntry>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => parse_lines(&contents, str::parse).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}", self.path.display(), err),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the most recent entry for the given profile, day and part.
+    pub fn latest<'a>(
+        entries: &'a [HistoryEntry],
+        profile: &str,
+        day: u32,
+        part: u32,
+    ) -> Option<&'a HistoryEntry> {
+        entries
+            .iter()
+            .rev()
+            .find(|entry| entry.profile == profile && entry.day == day && entry.part == part)
+    }
+
+    pub fn append(&self, entries: &[HistoryEntry]) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        for entry in entries {
+            writeln!(file, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(millis(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        let stats = Stats::from_samples(millis(&[4, 1, 2, 8]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_history_entry_round_trip() {
+        let entry = HistoryEntry {
+            timestamp: 1634567890,
+            commit: "e7a1ae5-dirty".to_string(),
+            profile: "default".to_string(),
+            day: 10,
+            part: 2,
+            benchmark: Benchmark {
+                runs: 3,
+                generator: Stats::from_samples(millis(&[1, 2, 3])),
+                solver: Stats::from_samples(millis(&[40, 50, 60])),
+            },
+        };
+        let line = entry.to_string();
+        assert_eq!(
+            line,
+            "1634567890 e7a1ae5-dirty default 10 2 3 \
+             1000000 2000000 3000000 40000000 50000000 60000000"
+        );
+        assert_eq!(line.parse(), Ok(entry));
+        let short = "1 abc default 1 1";
+        let err = short.parse::<HistoryEntry>().unwrap_err();
+        assert_eq!(err, ParseError::expected(short, short, "12 fields"));
+        assert_eq!(err.to_string(), "1:1: expected 12 fields, found `1`");
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The profile whose inputs live directly in `input/<year>`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Puzzle inputs on disk, keyed by profile and day.
+///
+/// The default profile's inputs are stored at `<root>/<year>/dayN.txt`, which is where
+/// `cargo aoc input` downloads them to. Any other profile is a subdirectory of that:
+/// `<root>/<year>/<profile>/dayN.txt`.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    root: PathBuf,
+    year: u32,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>, year: u32) -> Self {
+        Self {
+            root: root.into(),
+            year,
+        }
+    }
+
+    fn year_dir(&self) -> PathBuf {
+        self.root.join(self.year.to_string())
+    }
+
+    /// Lists all known profiles: the default profile first, followed by the others by name.
+    pub fn profiles(&self) -> io::Result<Vec<String>> {
+        let mut profiles = Vec::new();
+        match fs::read_dir(self.year_dir()) {
+            Ok(entries) => {
+                for entry in entries {
+                    let entry = entry?;
+                    if entry.file_type()?.is_dir() {
+                        if let Some(name) = entry.file_name().to_str() {
+                            profiles.push(name.to_string());
+                        }
+                    }
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        profiles.sort();
+        profiles.insert(0, DEFAULT_PROFILE.to_string());
+        Ok(profiles)
+    }
+
+    /// Returns the path to the input of the given profile and day.
+    pub fn path(&self, profile: &str, day: u32) -> PathBuf {
+        let file_name = format!("day{}.txt", day);
+        if profile == DEFAULT_PROFILE {
+            self.year_dir().join(file_name)
+        } else {
+            self.year_dir().join(profile).join(file_name)
+        }
+    }
+
+    /// Loads the input of the given profile and day, or `None` if there is no such input.
+    pub fn load(&self, profile: &str, day: u32) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(profile, day)) {
+            // Same as `aoc-runner`: ignore the trailing newline(s) at the end of the file.
+            Ok(input) => Ok(Some(input.trim_end_matches('\n').to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Stores the input of the given profile and day, replacing any existing input.
+    pub fn save(&self, profile: &str, day: u32, input: &str) -> io::Result<()> {
+        let path = self.path(profile, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn temp_store(name: &str) -> InputStore {
+        let root = env::temp_dir().join(format!("aoc-2015-inputs-{}", name));
+        let _ = fs::remove_dir_all(&root);
+        InputStore::new(root, 2015)
+    }
+
+    #[test]
+    fn test_path() {
+        let store = InputStore::new("input", 2015);
+        assert_eq!(
+            store.path(DEFAULT_PROFILE, 7),
+            PathBuf::from("input/2015/day7.txt")
+        );
+        assert_eq!(
+            store.path("alice", 7),
+            PathBuf::from("input/2015/alice/day7.txt")
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let store = temp_store("save-and-load");
+        assert_eq!(store.profiles().unwrap(), vec![DEFAULT_PROFILE]);
+        store.save(DEFAULT_PROFILE, 1, "(()\n").unwrap();
+        store.save("bob", 1, "))(\n").unwrap();
+        store.save("alice", 2, "1x2x3\n").unwrap();
+        assert_eq!(
+            store.profiles().unwrap(),
+            vec![DEFAULT_PROFILE, "alice", "bob"]
+        );
+        assert_eq!(
+            store.load(DEFAULT_PROFILE, 1).unwrap(),
+            Some("(()".to_string())
+        );
+        assert_eq!(store.load("bob", 1).unwrap(), Some("))(".to_string()));
+        assert_eq!(store.load("alice", 1).unwrap(), None);
+    }
+}
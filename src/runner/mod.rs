@@ -0,0 +1,13 @@
+//! Runs the solutions outside of `cargo aoc`, e.g. against the inputs of several profiles.
+
+pub use self::answers::*;
+pub use self::bench::*;
+pub use self::inputs::*;
+pub use self::solutions::*;
+pub use self::table::*;
+
+mod answers;
+mod bench;
+mod inputs;
+mod solutions;
+mod table;
@@ -0,0 +1,166 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+
+use aoc_runner::{ArcStr, Runner};
+
+use crate::util::ParseError;
+use crate::{
+    Day10Part1, Day10Part2, Day11Part1, Day11Part2, Day12Part1, Day12Part2, Day13Part1, Day13Part2,
+    Day14Part1, Day14Part2, Day15Part1, Day15Part2, Day16Part1, Day16Part2, Day17Part1, Day17Part2,
+    Day18Part1, Day18Part2, Day19Part1, Day19Part2, Day1Part1, Day1Part2, Day20Part1, Day20Part2,
+    Day21Part1, Day21Part2, Day22Part1, Day22Part2, Day23Part1, Day23Part2, Day24Part1, Day24Part2,
+    Day25Part1, Day2Part1, Day2Part2, Day3Part1, Day3Part2, Day4Part1, Day4Part2, Day5Part1,
+    Day5Part2, Day6Part1, Day6Part2, Day7Part1, Day7Part2, Day8Part1, Day8Part2, Day9Part1,
+    Day9Part2, Factory,
+};
+
+type Generate = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A single `#[aoc(dayN, partM)]` solution, together with its generator.
+#[derive(Copy, Clone)]
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    generate: Generate,
+}
+
+impl Solution {
+    fn new(day: u32, part: u32, generate: Generate) -> Self {
+        Self {
+            day,
+            part,
+            generate,
+        }
+    }
+
+    /// Runs the generator on the given input, returning a runner for the solution.
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Runner>, SolutionError> {
+        let generate = self.generate;
+        match catch_unwind(|| generate(ArcStr::from(input))) {
+            Ok(Ok(runner)) => Ok(runner),
+            Ok(Err(err)) => Err(SolutionError::Generate(err)),
+            Err(panic) => Err(SolutionError::Panic(panic_message(panic))),
+        }
+    }
+
+    /// Runs the solution on an already generated input, returning its answer.
+    pub fn solve(&self, runner: &dyn Runner) -> Result<String, SolutionError> {
+        match catch_unwind(AssertUnwindSafe(|| runner.try_run())) {
+            Ok(Ok(answer)) => Ok(answer.to_string()),
+            Ok(Err(err)) => Err(SolutionError::Run(err)),
+            Err(panic) => Err(SolutionError::Panic(panic_message(panic))),
+        }
+    }
+
+    /// Runs the generator and the solution on the given input, returning its answer.
+    pub fn run(&self, input: &str) -> Result<String, SolutionError> {
+        let runner = self.generate(input)?;
+        self.solve(runner.as_ref())
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day {} - Part {}", self.day, self.part)
+    }
+}
+
+#[derive(Debug)]
+pub enum SolutionError {
+    Generate(Box<dyn Error>),
+    Run(Box<dyn Error>),
+    Panic(String),
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionError::Generate(err) => write!(f, "failed while generating: {}", err),
+            SolutionError::Run(err) => write!(f, "failed while running: {}", err),
+            SolutionError::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl Error for SolutionError {}
+
+impl SolutionError {
+    /// Describes this error for the given input file, pointing at the offending line and column
+    /// for parse errors.
+    pub fn describe(&self, path: &Path) -> String {
+        match self {
+            SolutionError::Generate(err) => match err.downcast_ref::<ParseError>() {
+                Some(err) => format!("{}:{}", path.display(), err),
+                None => format!("{}: {}", path.display(), self),
+            },
+            _ => format!("{}: {}", path.display(), self),
+        }
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// All solutions in this crate, ordered by day and part.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new(1, 1, <Factory as Day1Part1>::day1_part1),
+        Solution::new(1, 2, <Factory as Day1Part2>::day1_part2),
+        Solution::new(2, 1, <Factory as Day2Part1>::day2_part1),
+        Solution::new(2, 2, <Factory as Day2Part2>::day2_part2),
+        Solution::new(3, 1, <Factory as Day3Part1>::day3_part1),
+        Solution::new(3, 2, <Factory as Day3Part2>::day3_part2),
+        Solution::new(4, 1, <Factory as Day4Part1>::day4_part1),
+        Solution::new(4, 2, <Factory as Day4Part2>::day4_part2),
+        Solution::new(5, 1, <Factory as Day5Part1>::day5_part1),
+        Solution::new(5, 2, <Factory as Day5Part2>::day5_part2),
+        Solution::new(6, 1, <Factory as Day6Part1>::day6_part1),
+        Solution::new(6, 2, <Factory as Day6Part2>::day6_part2),
+        Solution::new(7, 1, <Factory as Day7Part1>::day7_part1),
+        Solution::new(7, 2, <Factory as Day7Part2>::day7_part2),
+        Solution::new(8, 1, <Factory as Day8Part1>::day8_part1),
+        Solution::new(8, 2, <Factory as Day8Part2>::day8_part2),
+        Solution::new(9, 1, <Factory as Day9Part1>::day9_part1),
+        Solution::new(9, 2, <Factory as Day9Part2>::day9_part2),
+        Solution::new(10, 1, <Factory as Day10Part1>::day10_part1),
+        Solution::new(10, 2, <Factory as Day10Part2>::day10_part2),
+        Solution::new(11, 1, <Factory as Day11Part1>::day11_part1),
+        Solution::new(11, 2, <Factory as Day11Part2>::day11_part2),
+        Solution::new(12, 1, <Factory as Day12Part1>::day12_part1),
+        Solution::new(12, 2, <Factory as Day12Part2>::day12_part2),
+        Solution::new(13, 1, <Factory as Day13Part1>::day13_part1),
+        Solution::new(13, 2, <Factory as Day13Part2>::day13_part2),
+        Solution::new(14, 1, <Factory as Day14Part1>::day14_part1),
+        Solution::new(14, 2, <Factory as Day14Part2>::day14_part2),
+        Solution::new(15, 1, <Factory as Day15Part1>::day15_part1),
+        Solution::new(15, 2, <Factory as Day15Part2>::day15_part2),
+        Solution::new(16, 1, <Factory as Day16Part1>::day16_part1),
+        Solution::new(16, 2, <Factory as Day16Part2>::day16_part2),
+        Solution::new(17, 1, <Factory as Day17Part1>::day17_part1),
+        Solution::new(17, 2, <Factory as Day17Part2>::day17_part2),
+        Solution::new(18, 1, <Factory as Day18Part1>::day18_part1),
+        Solution::new(18, 2, <Factory as Day18Part2>::day18_part2),
+        Solution::new(19, 1, <Factory as Day19Part1>::day19_part1),
+        Solution::new(19, 2, <Factory as Day19Part2>::day19_part2),
+        Solution::new(20, 1, <Factory as Day20Part1>::day20_part1),
+        Solution::new(20, 2, <Factory as Day20Part2>::day20_part2),
+        Solution::new(21, 1, <Factory as Day21Part1>::day21_part1),
+        Solution::new(21, 2, <Factory as Day21Part2>::day21_part2),
+        Solution::new(22, 1, <Factory as Day22Part1>::day22_part1),
+        Solution::new(22, 2, <Factory as Day22Part2>::day22_part2),
+        Solution::new(23, 1, <Factory as Day23Part1>::day23_part1),
+        Solution::new(23, 2, <Factory as Day23Part2>::day23_part2),
+        Solution::new(24, 1, <Factory as Day24Part1>::day24_part1),
+        Solution::new(24, 2, <Factory as Day24Part2>::day24_part2),
+        Solution::new(25, 1, <Factory as Day25Part1>::day25_part1),
+    ]
+}
@@ -0,0 +1,76 @@
+use std::fmt::{Display, Formatter};
+
+/// A plain text table with left-aligned columns.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: Vec<String>) -> Self {
+        Self {
+            header,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths = vec![0; self.header.len()];
+        for row in Some(&self.header).into_iter().chain(self.rows.iter()) {
+            for (i, cell) in row.iter().enumerate() {
+                if i >= widths.len() {
+                    widths.push(0);
+                }
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+        widths
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let widths = self.column_widths();
+        let write_row = |f: &mut Formatter<'_>, row: &[String]| {
+            let line = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            writeln!(f, "{}", line.trim_end())
+        };
+        write_row(f, &self.header)?;
+        let separator = widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<_>>()
+            .join("-+-");
+        writeln!(f, "{}", separator)?;
+        for row in self.rows.iter() {
+            write_row(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut table = Table::new(vec!["Day".to_string(), "Answer".to_string()]);
+        table.push(vec!["1".to_string(), "138".to_string()]);
+        table.push(vec!["10".to_string(), "4666278".to_string()]);
+        assert_eq!(
+            table.to_string(),
+            "Day | Answer\n----+--------\n1   | 138\n10  | 4666278\n"
+        );
+    }
+}
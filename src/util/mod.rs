@@ -1,3 +1,9 @@
+pub use self::parse::*;
+pub use self::random::*;
+pub use self::subsets::*;
 pub use self::vector::*;
 
+mod parse;
+mod random;
+mod subsets;
 mod vector;
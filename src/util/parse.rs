@@ -0,0 +1,235 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error while parsing puzzle input, pointing at the offending position.
+///
+/// Lines and columns are 1-based, and relative to the string that was being parsed.
+/// Use [`ParseError::offset`] to make them relative to an enclosing string instead.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// Expected some token, but found something else (or nothing at all).
+    Expected {
+        expected: String,
+        found: Option<String>,
+    },
+    /// Found a token that is not one of the known keywords, e.g. an unknown gate.
+    Unknown { what: &'static str, token: String },
+    /// Found a token of the right shape, but with an invalid value, e.g. a number that overflows.
+    Invalid {
+        what: &'static str,
+        token: String,
+        reason: String,
+    },
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// Creates an error located at `at`, which must be a slice of `s`.
+    pub fn at(s: &str, at: &str, kind: ParseErrorKind) -> Self {
+        let (line, column) = position(s, at);
+        Self::new(line, column, kind)
+    }
+
+    /// Expected `expected` at the start of `at`.
+    pub fn expected(s: &str, at: &str, expected: impl Into<String>) -> Self {
+        let found = at
+            .split_whitespace()
+            .next()
+            .map(|token| format!("`{}`", token));
+        Self::at(
+            s,
+            at,
+            ParseErrorKind::Expected {
+                expected: expected.into(),
+                found,
+            },
+        )
+    }
+
+    /// `token` is not a known `what`.
+    pub fn unknown(s: &str, token: &str, what: &'static str) -> Self {
+        Self::at(
+            s,
+            token,
+            ParseErrorKind::Unknown {
+                what,
+                token: token.to_string(),
+            },
+        )
+    }
+
+    /// `token` is not a valid `what`.
+    pub fn invalid(s: &str, token: &str, what: &'static str, reason: impl Display) -> Self {
+        Self::at(
+            s,
+            token,
+            ParseErrorKind::Invalid {
+                what,
+                token: token.to_string(),
+                reason: reason.to_string(),
+            },
+        )
+    }
+
+    /// Moves this error from being relative to `inner` to being relative to `outer`.
+    pub fn offset(mut self, outer: &str, inner: &str) -> Self {
+        let (line, column) = position(outer, inner);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Expected { expected, found } => match found {
+                Some(found) => write!(f, "expected {}, found {}", expected, found),
+                None => write!(f, "expected {}, found end of line", expected),
+            },
+            ParseErrorKind::Unknown { what, token } => write!(f, "unknown {} `{}`", what, token),
+            ParseErrorKind::Invalid {
+                what,
+                token,
+                reason,
+            } => write!(f, "invalid {} `{}`: {}", what, token, reason),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Returns the 1-based line and column at which `inner` starts inside `outer`.
+fn position(outer: &str, inner: &str) -> (usize, usize) {
+    let offset = (inner.as_ptr() as usize).wrapping_sub(outer.as_ptr() as usize);
+    debug_assert!(offset <= outer.len(), "not a slice of the parsed string");
+    let before = &outer[..offset.min(outer.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Returns the empty slice at the very end of `s`, to point at "end of line" errors.
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+/// Quotes a literal token for use in an "expected ..." message.
+fn quote(token: &str) -> String {
+    match token.trim() {
+        "" => "whitespace".to_string(),
+        token => format!("`{}`", token),
+    }
+}
+
+/// Parses a number from `token`, which must be a slice of `s`.
+pub fn parse_number<T>(s: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|err| ParseError::invalid(s, token, "number", err))
+}
+
+/// Strips `prefix` from the start of `at`, which must be a slice of `s`.
+pub fn strip_prefix<'a>(s: &str, at: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    at.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::expected(s, at, quote(prefix)))
+}
+
+/// Strips `suffix` from the end of `at`, which must be a slice of `s`.
+pub fn strip_suffix<'a>(s: &str, at: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    at.strip_suffix(suffix)
+        .ok_or_else(|| ParseError::expected(s, end_of(at), quote(suffix)))
+}
+
+/// Splits `at` on the first occurrence of `delimiter`, where `at` must be a slice of `s`.
+pub fn split_once<'a>(
+    s: &str,
+    at: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    at.split_once(delimiter)
+        .ok_or_else(|| ParseError::expected(s, end_of(at), quote(delimiter)))
+}
+
+/// Parses every line of `input` with `parse`, reporting errors relative to the whole input.
+pub fn parse_lines<T>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|err| err.offset(input, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let s = "abc\ndef\nghi";
+        assert_eq!(position(s, &s[0..]), (1, 1));
+        assert_eq!(position(s, &s[2..]), (1, 3));
+        assert_eq!(position(s, &s[4..]), (2, 1));
+        assert_eq!(position(s, &s[9..]), (3, 2));
+        assert_eq!(position(s, end_of(s)), (3, 4));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "1\n2\n3x\n4";
+        assert_eq!(
+            parse_lines(input, |line| parse_number::<u32>(line, line)),
+            Err(ParseError::new(
+                3,
+                1,
+                ParseErrorKind::Invalid {
+                    what: "number",
+                    token: "3x".to_string(),
+                    reason: "invalid digit found in string".to_string(),
+                }
+            ))
+        );
+        assert_eq!(
+            parse_lines(input.replace("3x", "3").as_str(), |line| parse_number(
+                line, line
+            )),
+            Ok(vec![1u32, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let s = "x XOR y -> z";
+        let op = s.split(' ').nth(1).unwrap();
+        let err = ParseError::unknown(s, op, "gate");
+        assert_eq!(err.to_string(), "1:3: unknown gate `XOR`");
+        let err = split_once(s, &s[8..], " => ").unwrap_err();
+        assert_eq!(err.to_string(), "1:13: expected `=>`, found end of line");
+    }
+}
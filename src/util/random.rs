@@ -0,0 +1,58 @@
+/// A small seeded pseudo-random number generator (SplitMix64), so that simulations can be
+/// repeated exactly.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        // Rejects the top of the range that would make some numbers more likely than others.
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// Returns a number in `0.0..1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(42);
+        let mut other = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(rng.next_u64(), other.next_u64());
+        }
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[rng.below(6) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| (900..1100).contains(&count)));
+        let x = rng.next_f64();
+        assert!((0.0..1.0).contains(&x));
+    }
+}
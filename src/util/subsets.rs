@@ -0,0 +1,272 @@
+//! Subset sums over small sets of items, with subsets as bitmasks where bit `i` stands for item
+//! `i`.
+//!
+//! Everything here splits the items in two halves and combines the subsets of one half with
+//! those of the other ("meet in the middle"), which takes time and memory proportional to
+//! `2^(n/2)` rather than `2^n`. That keeps sets of up to about 40 items practical.
+
+use std::collections::HashMap;
+
+/// Returns the sum of every subset of `weights`, indexed by bitmask.
+fn subset_sums(weights: &[i32]) -> Vec<i64> {
+    let mut sums = vec![0; 1 << weights.len()];
+    for mask in 1..sums.len() {
+        let lowest = mask.trailing_zeros() as usize;
+        sums[mask] = sums[mask & (mask - 1)] + i64::from(weights[lowest]);
+    }
+    sums
+}
+
+/// The subset sums of both halves of a set of items.
+struct Halves {
+    /// The number of items in the low half, which come first.
+    low_len: usize,
+    low: Vec<i64>,
+    high: Vec<i64>,
+}
+
+impl Halves {
+    fn new(weights: &[i32]) -> Self {
+        assert!(weights.len() < 64, "too many items for a bitmask");
+        let low_len = weights.len() / 2;
+        Halves {
+            low_len,
+            low: subset_sums(&weights[..low_len]),
+            high: subset_sums(&weights[low_len..]),
+        }
+    }
+}
+
+/// Returns the items of `weights` in the subset `mask`.
+pub fn subset(weights: &[i32], mask: u64) -> Vec<i32> {
+    weights
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| mask & 1 << i != 0)
+        .map(|(_, &weight)| weight)
+        .collect()
+}
+
+/// Counts the subsets of `weights` that add up to `sum`, by their number of items.
+///
+/// The result has an entry for every length from 0 to the number of items.
+pub fn count_subsets_by_len(weights: &[i32], sum: i64) -> Vec<u64> {
+    let halves = Halves::new(weights);
+    // The number of subsets of the low half with every sum, by their number of items.
+    let mut low = HashMap::<i64, Vec<u64>>::new();
+    for (mask, &low_sum) in halves.low.iter().enumerate() {
+        let counts = low
+            .entry(low_sum)
+            .or_insert_with(|| vec![0; halves.low_len + 1]);
+        counts[mask.count_ones() as usize] += 1;
+    }
+    let mut counts = vec![0; weights.len() + 1];
+    for (mask, &high_sum) in halves.high.iter().enumerate() {
+        if let Some(low_counts) = low.get(&(sum - high_sum)) {
+            let high_len = mask.count_ones() as usize;
+            for (low_len, &count) in low_counts.iter().enumerate() {
+                counts[high_len + low_len] += count;
+            }
+        }
+    }
+    counts
+}
+
+/// Returns every subset of `weights` that adds up to `sum`.
+pub fn subsets_with_sum(weights: &[i32], sum: i64) -> Vec<u64> {
+    let halves = Halves::new(weights);
+    let mut low = HashMap::<i64, Vec<u64>>::new();
+    for (mask, &low_sum) in halves.low.iter().enumerate() {
+        low.entry(low_sum).or_default().push(mask as u64);
+    }
+    let mut subsets = vec![];
+    for (mask, &high_sum) in halves.high.iter().enumerate() {
+        if let Some(lows) = low.get(&(sum - high_sum)) {
+            let high = (mask as u64) << halves.low_len;
+            subsets.extend(lows.iter().map(|&low| high | low));
+        }
+    }
+    subsets
+}
+
+/// Finds the subset of `weights` that adds up to `sum` and is accepted, with the fewest items,
+/// and then the lowest `key`.
+///
+/// This meets in the middle one number of items at a time, and stops at the first number of
+/// items with an accepted subset, so it never builds the larger subsets at all.
+pub fn min_subset_with_sum<K, F, A>(
+    weights: &[i32],
+    sum: i64,
+    mut key: F,
+    mut accept: A,
+) -> Option<u64>
+where
+    K: Ord,
+    F: FnMut(u64) -> K,
+    A: FnMut(u64) -> bool,
+{
+    let halves = Halves::new(weights);
+    let high_len = weights.len() - halves.low_len;
+    let mut low = HashMap::<(i64, u32), Vec<u64>>::new();
+    for (mask, &low_sum) in halves.low.iter().enumerate() {
+        let len = mask.count_ones();
+        low.entry((low_sum, len)).or_default().push(mask as u64);
+    }
+    let mut high = vec![vec![]; high_len + 1];
+    for mask in 0..halves.high.len() {
+        high[mask.count_ones() as usize].push(mask);
+    }
+    for len in 0..=weights.len() {
+        let mut candidates = vec![];
+        for (high_len, highs) in high.iter().enumerate().take(len + 1) {
+            let low_len = (len - high_len) as u32;
+            for &mask in highs.iter() {
+                if let Some(lows) = low.get(&(sum - halves.high[mask], low_len)) {
+                    let high = (mask as u64) << halves.low_len;
+                    candidates.extend(lows.iter().map(|&low| (key(high | low), high | low)));
+                }
+            }
+        }
+        candidates.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if let Some((_, subset)) = candidates.into_iter().find(|&(_, subset)| accept(subset)) {
+            return Some(subset);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks every subset one by one.
+    fn brute_force(weights: &[i32], sum: i64) -> Vec<u64> {
+        (0..1u64 << weights.len())
+            .filter(|&mask| {
+                subset(weights, mask)
+                    .iter()
+                    .map(|&w| i64::from(w))
+                    .sum::<i64>()
+                    == sum
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_subsets() {
+        let weights = [20, 15, 10, 5, 5, -3, 8];
+        for sum in -5..60 {
+            let expected = brute_force(&weights, sum);
+            let mut subsets = subsets_with_sum(&weights, sum);
+            subsets.sort_unstable();
+            assert_eq!(subsets, expected);
+
+            let counts = count_subsets_by_len(&weights, sum);
+            assert_eq!(counts.len(), weights.len() + 1);
+            for (len, &count) in counts.iter().enumerate() {
+                let expected = expected
+                    .iter()
+                    .filter(|mask| mask.count_ones() as usize == len);
+                assert_eq!(count, expected.count() as u64);
+            }
+        }
+        assert_eq!(subset(&weights, 0b1000101), vec![20, 10, 8]);
+        assert_eq!(count_subsets_by_len(&[], 0), vec![1]);
+        assert!(subsets_with_sum(&[], 1).is_empty());
+    }
+
+    #[test]
+    fn test_min_subset_with_sum() {
+        let weights = [20, 15, 10, 5, 5];
+        let everything = |_| true;
+        let smallest = min_subset_with_sum(&weights, 30, |mask| mask, everything);
+        assert_eq!(
+            smallest.map(|mask| subset(&weights, mask)),
+            Some(vec![20, 10])
+        );
+        // The lowest key among the subsets with the fewest items, and not among all of them.
+        let smallest = min_subset_with_sum(&weights, 25, |mask| mask, everything);
+        assert_eq!(
+            smallest.map(|mask| subset(&weights, mask)),
+            Some(vec![15, 10])
+        );
+        let without_20 = |mask| mask & 1 == 0;
+        let smallest = min_subset_with_sum(&weights, 30, |mask| mask, without_20);
+        assert_eq!(
+            smallest.map(|mask| subset(&weights, mask)),
+            Some(vec![15, 10, 5])
+        );
+        assert_eq!(
+            min_subset_with_sum(&weights, 100, |mask| mask, everything),
+            None
+        );
+    }
+
+    #[test]
+    fn test_many_items() {
+        // Far too many subsets to check one by one.
+        let weights = (1..=36).collect::<Vec<i32>>();
+        let counts = count_subsets_by_len(&weights, 333);
+        // The largest 10 items only add up to 315.
+        assert_eq!(counts[10], 0);
+        assert!(counts[11] > 0);
+        // 333 is half the total, so the complement of every subset adds up to it as well.
+        for len in 0..=36 {
+            assert_eq!(counts[len], counts[36 - len]);
+        }
+    }
+
+    /// Every subset of positive `weights` that adds up to `sum`, enumerated recursively like
+    /// days 17 and 24 did before this engine.
+    fn recursive(weights: &[i32], sum: i64) -> Box<dyn Iterator<Item = Vec<i32>> + '_> {
+        match weights.split_first() {
+            None if sum == 0 => Box::new(std::iter::once(vec![])),
+            None => Box::new(std::iter::empty()),
+            Some((&first, rest)) if i64::from(first) > sum => recursive(rest, sum),
+            Some((&first, rest)) => {
+                let with_first = recursive(rest, sum - i64::from(first)).map(move |mut subset| {
+                    subset.insert(0, first);
+                    subset
+                });
+                Box::new(with_first.chain(recursive(rest, sum)))
+            }
+        }
+    }
+
+    /// Compares the engine against the recursive enumeration. Run it with
+    /// `cargo test --release -- --ignored --nocapture bench_subsets`.
+    #[test]
+    #[ignore]
+    fn bench_subsets() {
+        use std::time::Instant;
+
+        use crate::util::Rng;
+
+        let mut rng = Rng::new(2015);
+        for &len in [16, 20, 24, 28].iter() {
+            let weights = (0..len)
+                .map(|_| 1 + rng.below(100) as i32)
+                .collect::<Vec<_>>();
+            let sum = i64::from(weights.iter().sum::<i32>() / 3);
+
+            let start = Instant::now();
+            let counted = count_subsets_by_len(&weights, sum).iter().sum::<u64>();
+            let count_time = start.elapsed();
+
+            let start = Instant::now();
+            let listed = subsets_with_sum(&weights, sum).len() as u64;
+            let list_time = start.elapsed();
+
+            let start = Instant::now();
+            let expected = recursive(&weights, sum).count() as u64;
+            let recursive_time = start.elapsed();
+
+            assert_eq!(counted, expected);
+            assert_eq!(listed, expected);
+            println!(
+                "{} items, {} subsets: recursive {:?}, count {:?}, enumerate {:?}",
+                len, expected, recursive_time, count_time, list_time
+            );
+        }
+    }
+}